@@ -3,39 +3,88 @@ use std::collections::HashMap;
 type Employee<'a> = &'a str;
 type Manager<'a> = &'a str;
 
+/// Why an org chart can't be counted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrgChartError {
+    /// These employees report to each other in a loop, each one to the next and the last one to
+    /// the first, starting from the first in alphabetical order.
+    Cycle(Vec<String>),
+    /// Someone is listed as a manager, but not as an employee, so we don't know who they report to.
+    DanglingManager(String),
+    /// Nobody reports to themselves, so there's no one at the top. Without a cycle, that means
+    /// everyone reports up to someone who isn't in the chart at all.
+    NoRoot,
+    /// More than one person reports to themselves, in alphabetical order.
+    MultipleRoots(Vec<String>),
+}
+
+impl std::fmt::Display for OrgChartError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            OrgChartError::Cycle(path) => {
+                write!(
+                    f,
+                    "{} -> {} is a reporting cycle",
+                    path.join(" -> "),
+                    path[0]
+                )
+            }
+            OrgChartError::DanglingManager(name) => {
+                write!(f, "{} manages someone, but isn't an employee", name)
+            }
+            OrgChartError::NoRoot => write!(f, "nobody reports to themselves"),
+            OrgChartError::MultipleRoots(roots) => {
+                write!(f, "{} all report to themselves", roots.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for OrgChartError {}
+
 /// Given a dictionary that contains mapping of an employee and their manager, returns a dictionary
 /// that contains the TOTAL number of employees working under each manager.
 ///
 /// # Arguments
 ///
-/// * `dict` - A HashMap<char, char> containing the mapping of an employee to their manager
+/// * `dict` - A HashMap<&str, &str> containing the mapping of an employee to their manager. Whoever
+///   reports to themselves is at the top, and there has to be exactly one of them, unless the dict
+///   is empty
 ///
 /// # Returns
 ///
-/// A HashMap<char, u32> containing the total number of employees working under each manager
+/// A HashMap<&str, u32> containing the total number of employees working under each employee, or
+/// an [`OrgChartError`] if the dict isn't a single tree. The errors are checked in this order:
+/// cycles, then the number of people at the top, then managers who aren't employees.
 ///
 /// # Example
 ///
 /// ```
-/// let dict = {
-///     "A": "C",
-///     "B": "C",
-///     "C": "F",
-///     "D": "E",
-///     "E": "F",
-///     "F": "F"
-/// };
-///
-/// let result = employee_count(&dict);
-///
-/// assert_eq!(result, {
-///     "A": 0,
-///     "B": 0,
-///     "C": 2,
-///     "D": 0,
-///     "E": 1,
-///     "F": 5
-/// });
+/// use std::collections::HashMap;
+///
+/// use week1::employee_count::{employee_count, OrgChartError};
+///
+/// let dict = HashMap::from([
+///     ("A", "C"),
+///     ("B", "C"),
+///     ("C", "F"),
+///     ("D", "E"),
+///     ("E", "F"),
+///     ("F", "F"),
+/// ]);
+///
+/// let result = employee_count(&dict).unwrap();
+///
+/// assert_eq!(
+///     result,
+///     HashMap::from([("A", 0), ("B", 0), ("C", 2), ("D", 0), ("E", 1), ("F", 5)])
+/// );
+///
+/// let dict = HashMap::from([("A", "B"), ("B", "A")]);
+/// assert_eq!(
+///     employee_count(&dict),
+///     Err(OrgChartError::Cycle(vec!["A".to_string(), "B".to_string()]))
+/// );
 /// ```
 /// # Explanation
 ///
@@ -48,61 +97,95 @@ type Manager<'a> = &'a str;
 ///
 /// # Approach
 ///
-/// Create an adjacency list of the managers and their employees. If an employee doesn't have an
-/// entry in the adjacency list, they have no employees reporting to them, so their final count is 0.
-/// In other cases, perform DFS to count the number of employees reporting to each manager.
-/// Memoize.
+/// Count from the bottom up: count how many direct reports each manager is waiting on, and start
+/// with the employees who have nobody reporting to them. Once an employee's count is final, add it
+/// (plus one for them) to their manager's, and once a manager isn't waiting on anyone any more,
+/// their count is final too. This never recurses, so deep org charts can't overflow the stack.
+///
+/// Anyone whose count never becomes final is waiting on someone who's waiting on them, which
+/// means they're part of a cycle, and we walk it from the first of them alphabetically to report it.
+/// Then check that exactly one employee reports to themselves, and that every manager is an
+/// employee.
 ///
 /// # Time and Space Complexity
 ///
 /// The time complexity is O(n), where n is the number of employees - we iterate over each
-/// employee once to create the adjacency list and DFS is also O(n) since each employee is
-/// visited/computed once with memoization. Space complexity is O(n) as well since we store the
-/// adjacency list and memoization results (which are also the final results).
-pub fn employee_count<'a>(dict: &HashMap<Employee<'a>, Manager<'a>>) -> HashMap<Manager<'a>, u32> {
-    // adjacency list
-    let mut manager_employees: HashMap<Manager, Vec<Employee>> = HashMap::new();
-
-    // fill adj list
-    dict.iter().for_each(|(employee, manager)| {
-        manager_employees
-            .entry(*manager)
-            .or_default()
-            .push(*employee);
-    });
-
-    let mut result: HashMap<Manager, u32> = HashMap::new();
-
-    // dfs to count employees
-    fn dfs(
-        manager: Manager,
-        manager_employees: &HashMap<Manager, Vec<Employee>>,
-        result: &HashMap<Manager, u32>,
-    ) -> u32 {
-        if !manager_employees.contains_key(&manager) {
-            // base case: no employees
-            return 0;
+/// employee once to count their direct reports, and each one is finished exactly once after that,
+/// plus O(n log n) to sort the names that go into an error. Space complexity is O(n) as well since
+/// we store the number of reports we're waiting on and the results.
+pub fn employee_count<'a>(
+    dict: &HashMap<Employee<'a>, Manager<'a>>,
+) -> Result<HashMap<Manager<'a>, u32>, OrgChartError> {
+    // how many direct reports each manager is still waiting on
+    let mut waiting: HashMap<Manager, usize> = HashMap::new();
+    for (&employee, &manager) in dict {
+        if employee != manager {
+            *waiting.entry(manager).or_default() += 1;
         }
-        if result.contains_key(&manager) {
-            // memoization
-            return *result.get(&manager).unwrap();
+    }
+
+    let mut result: HashMap<Manager, u32> = dict.keys().map(|&employee| (employee, 0)).collect();
+    let mut finished = dict
+        .keys()
+        .copied()
+        .filter(|employee| !waiting.contains_key(employee))
+        .collect::<Vec<_>>();
+    let mut done = 0;
+    while let Some(employee) = finished.pop() {
+        done += 1;
+        let manager = dict[employee];
+        if manager == employee {
+            continue;
         }
-        let mut count = 0;
-        for &employee in manager_employees.get(&manager).unwrap() {
-            if employee == manager {
-                // can't be your own employee
-                continue;
+        let count = result[employee] + 1;
+        let left = waiting.get_mut(manager).unwrap();
+        *left -= 1;
+        // a manager who isn't an employee has nobody above them to pass their count on to
+        if let Some(total) = result.get_mut(manager) {
+            *total += count;
+            if *left == 0 {
+                finished.push(manager);
             }
-            count += 1 + dfs(employee, manager_employees, result);
         }
-        count
     }
 
-    dict.keys().for_each(|&manager| {
-        result.insert(manager, dfs(manager, &manager_employees, &result));
-    });
+    if done < dict.len() {
+        // everyone still waiting is on a cycle, since a cycle can't have anyone above it
+        let start = dict
+            .keys()
+            .copied()
+            .filter(|employee| waiting.get(employee).is_some_and(|&left| left > 0))
+            .min()
+            .unwrap();
+        let mut path = vec![start.to_string()];
+        let mut employee = dict[start];
+        while employee != start {
+            path.push(employee.to_string());
+            employee = dict[employee];
+        }
+        return Err(OrgChartError::Cycle(path));
+    }
 
-    result
+    let mut roots = dict
+        .iter()
+        .filter(|(employee, manager)| employee == manager)
+        .map(|(&employee, _)| employee.to_string())
+        .collect::<Vec<_>>();
+    match roots.len() {
+        0 if !dict.is_empty() => return Err(OrgChartError::NoRoot),
+        0 | 1 => {}
+        _ => {
+            roots.sort();
+            return Err(OrgChartError::MultipleRoots(roots));
+        }
+    }
+
+    let dangling = dict.values().filter(|manager| !dict.contains_key(*manager));
+    if let Some(manager) = dangling.min() {
+        return Err(OrgChartError::DanglingManager(manager.to_string()));
+    }
+
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -119,7 +202,7 @@ mod test {
         dict.insert("E", "F");
         dict.insert("F", "F");
 
-        let result = employee_count(&dict);
+        let result = employee_count(&dict).unwrap();
         assert_eq!(result.get(&"A"), Some(&0));
         assert_eq!(result.get(&"B"), Some(&0));
         assert_eq!(result.get(&"C"), Some(&2));
@@ -138,7 +221,7 @@ mod test {
         dict.insert("E", "F");
         dict.insert("F", "F");
 
-        let result = employee_count(&dict);
+        let result = employee_count(&dict).unwrap();
         assert_eq!(result.get(&"A"), Some(&0));
         assert_eq!(result.get(&"B"), Some(&1));
         assert_eq!(result.get(&"C"), Some(&2));
@@ -150,7 +233,7 @@ mod test {
     #[test]
     fn test_employee_count_empty() {
         let dict = HashMap::new();
-        let result = employee_count(&dict);
+        let result = employee_count(&dict).unwrap();
         assert_eq!(result.len(), 0);
     }
 
@@ -174,9 +257,95 @@ mod test {
         dict.insert("C1B", "C1");
         dict.insert("C1C", "C1");
 
-        let result = employee_count(&dict);
+        let result = employee_count(&dict).unwrap();
         assert_eq!(result.get(&"A"), Some(&14));
         assert_eq!(result.get(&"B"), Some(&5));
         assert_eq!(result.get(&"C"), Some(&4));
     }
+
+    #[test]
+    fn test_employee_count_cycle() {
+        let mut dict = HashMap::new();
+        dict.insert("A", "B");
+        dict.insert("B", "C");
+        dict.insert("C", "A");
+        dict.insert("D", "A");
+        dict.insert("F", "F");
+
+        let error = employee_count(&dict).unwrap_err();
+        assert_eq!(
+            error,
+            OrgChartError::Cycle(vec!["A".to_string(), "B".to_string(), "C".to_string()])
+        );
+        assert_eq!(error.to_string(), "A -> B -> C -> A is a reporting cycle");
+    }
+
+    #[test]
+    fn test_employee_count_cycle_without_root() {
+        let mut dict = HashMap::new();
+        dict.insert("A", "B");
+        dict.insert("B", "A");
+
+        assert_eq!(
+            employee_count(&dict),
+            Err(OrgChartError::Cycle(vec!["A".to_string(), "B".to_string()]))
+        );
+    }
+
+    #[test]
+    fn test_employee_count_no_root() {
+        // the CEO is missing altogether, so everyone ends up reporting to someone who isn't here
+        let mut dict = HashMap::new();
+        dict.insert("A", "C");
+        dict.insert("B", "C");
+        dict.insert("C", "F");
+
+        assert_eq!(employee_count(&dict), Err(OrgChartError::NoRoot));
+    }
+
+    #[test]
+    fn test_employee_count_dangling_manager() {
+        let mut dict = HashMap::new();
+        dict.insert("A", "C");
+        dict.insert("B", "Z");
+        dict.insert("C", "C");
+
+        let error = employee_count(&dict).unwrap_err();
+        assert_eq!(error, OrgChartError::DanglingManager("Z".to_string()));
+        assert_eq!(
+            error.to_string(),
+            "Z manages someone, but isn't an employee"
+        );
+    }
+
+    #[test]
+    fn test_employee_count_multiple_roots() {
+        let mut dict = HashMap::new();
+        dict.insert("A", "F");
+        dict.insert("G", "G");
+        dict.insert("F", "F");
+
+        assert_eq!(
+            employee_count(&dict),
+            Err(OrgChartError::MultipleRoots(vec![
+                "F".to_string(),
+                "G".to_string()
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_employee_count_deep() {
+        // far deeper than the stack would go with recursion
+        let names = (0..200_000).map(|i| i.to_string()).collect::<Vec<_>>();
+        let mut dict = HashMap::new();
+        dict.insert(names[0].as_str(), names[0].as_str());
+        for pair in names.windows(2) {
+            dict.insert(pair[1].as_str(), pair[0].as_str());
+        }
+
+        let result = employee_count(&dict).unwrap();
+        assert_eq!(result.get("0"), Some(&199_999));
+        assert_eq!(result.get("199999"), Some(&0));
+    }
 }
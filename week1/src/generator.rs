@@ -0,0 +1,419 @@
+use std::ops::Range;
+
+use crate::grid::Grid;
+use crate::traversal::spiral::{Corner, Rotation, SpiralOrder};
+use crate::traversal::{Spiral, Traversal};
+
+/// The reasons a generator can't build the matrix it was asked for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GeneratorError {
+    /// The matrix would have no rows or no columns.
+    Empty { rows: usize, cols: usize },
+    /// rows * cols is more than a usize can count.
+    TooBig { rows: usize, cols: usize },
+    /// There is nothing in the range to pick random numbers from.
+    EmptyRange { start: i64, end: i64 },
+}
+
+impl std::fmt::Display for GeneratorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            GeneratorError::Empty { rows, cols } => {
+                write!(f, "a {}x{} matrix has no elements", rows, cols)
+            }
+            GeneratorError::TooBig { rows, cols } => {
+                write!(f, "a {}x{} matrix has too many elements", rows, cols)
+            }
+            GeneratorError::EmptyRange { start, end } => {
+                write!(f, "there are no numbers in {}..{}", start, end)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GeneratorError {}
+
+/// Builds a rows x cols matrix whose element at (row, col) is `f(row, col)`. Every other generator
+/// is built on this one.
+///
+/// # Arguments
+///
+/// * `rows` - The number of rows, at least 1
+/// * `cols` - The number of columns, at least 1
+/// * `f` - Computes the element at (row, col), called once per element in row-major order
+///
+/// # Returns
+///
+/// The matrix, or an error if it would be empty or too big to count its elements
+///
+/// # Example
+///
+/// ```
+/// use week1::generator::{generate, GeneratorError};
+///
+/// let times = generate(3, 3, |row, col| (row + 1) * (col + 1)).unwrap();
+/// assert_eq!(times.row(2), &[3, 6, 9]);
+///
+/// assert_eq!(
+///     generate(0, 3, |row, col| row + col),
+///     Err(GeneratorError::Empty { rows: 0, cols: 3 })
+/// );
+/// ```
+pub fn generate<T, F>(rows: usize, cols: usize, mut f: F) -> Result<Grid<T>, GeneratorError>
+where
+    F: FnMut(usize, usize) -> T,
+{
+    if rows == 0 || cols == 0 {
+        return Err(GeneratorError::Empty { rows, cols });
+    }
+    let len = rows
+        .checked_mul(cols)
+        .ok_or(GeneratorError::TooBig { rows, cols })?;
+
+    let values = (0..len).map(|i| f(i / cols, i % cols));
+    // exactly rows * cols values, so this can't fail
+    Ok(Grid::from_iter(rows, cols, values).unwrap())
+}
+
+/// The rectangular version of [`create_matrix`](crate::traversal::spiral::create_matrix): a
+/// rows x cols matrix counting up from 0 in row-major order.
+///
+/// # Example
+///
+/// ```
+/// use week1::generator::counting;
+///
+/// let matrix = counting(2, 4).unwrap();
+/// assert_eq!(matrix.into_rows(), vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7]]);
+/// ```
+pub fn counting(rows: usize, cols: usize) -> Result<Grid<usize>, GeneratorError> {
+    generate(rows, cols, |row, col| row * cols + col)
+}
+
+/// A rows x cols matrix with every element set to `value`.
+///
+/// # Example
+///
+/// ```
+/// use week1::generator::constant;
+///
+/// let matrix = constant(2, 2, "x").unwrap();
+/// assert_eq!(matrix.as_slice(), &["x", "x", "x", "x"]);
+/// ```
+pub fn constant<T: Clone>(rows: usize, cols: usize, value: T) -> Result<Grid<T>, GeneratorError> {
+    generate(rows, cols, |_, _| value.clone())
+}
+
+/// A rows x cols matrix with ones on the main diagonal and zeros everywhere else. For a square
+/// matrix that's the identity matrix.
+///
+/// # Example
+///
+/// ```
+/// use week1::generator::identity;
+///
+/// let matrix = identity::<i32>(2, 3).unwrap();
+/// assert_eq!(matrix.into_rows(), vec![vec![1, 0, 0], vec![0, 1, 0]]);
+/// ```
+pub fn identity<T: From<u8>>(rows: usize, cols: usize) -> Result<Grid<T>, GeneratorError> {
+    generate(rows, cols, |row, col| T::from((row == col) as u8))
+}
+
+/// A rows x cols matrix of pseudo-random numbers in `range`. The same seed always gives the same
+/// matrix, on every machine.
+///
+/// # Arguments
+///
+/// * `rows` - The number of rows, at least 1
+/// * `cols` - The number of columns, at least 1
+/// * `range` - The numbers to pick from, which must not be empty
+/// * `seed` - Seeds the [`Rng`]
+///
+/// # Example
+///
+/// ```
+/// use week1::generator::random;
+///
+/// let dice = random(3, 4, 1..7, 42).unwrap();
+/// assert!(dice.as_slice().iter().all(|x| (1..7).contains(x)));
+/// assert_eq!(dice, random(3, 4, 1..7, 42).unwrap());
+/// ```
+pub fn random(
+    rows: usize,
+    cols: usize,
+    range: Range<i64>,
+    seed: u64,
+) -> Result<Grid<i64>, GeneratorError> {
+    if range.is_empty() {
+        return Err(GeneratorError::EmptyRange {
+            start: range.start,
+            end: range.end,
+        });
+    }
+    let mut rng = Rng::new(seed);
+    generate(rows, cols, |_, _| rng.range(range.clone()))
+}
+
+/// The numbers of the Ulam spiral: counting up from 1 along a counterclockwise spiral that winds
+/// out to the bottom right corner, so the last number, rows * cols, always ends up there.
+///
+/// On an odd square that's the classic Ulam spiral, with 1 in the center and 2 to its right. Any
+/// other rectangle works too, but 1 and 2 go wherever the outward spiral that ends in the bottom
+/// right corner has to start, e.g. on a 2x4 matrix 1 is in the top right corner and 2 is to its
+/// left.
+///
+/// # Example
+///
+/// ```
+/// use week1::generator::ulam_numbers;
+///
+/// let numbers = ulam_numbers(3, 3).unwrap();
+/// assert_eq!(
+///     numbers.into_rows(),
+///     vec![vec![5, 4, 3], vec![6, 1, 2], vec![7, 8, 9]]
+/// );
+/// ```
+pub fn ulam_numbers(rows: usize, cols: usize) -> Result<Grid<usize>, GeneratorError> {
+    let order = SpiralOrder::new()
+        .corner(Corner::BottomRight)
+        .rotation(Rotation::CounterClockwise)
+        .outward();
+    let spiral = Spiral::with_order(rows, cols, order);
+    generate(rows, cols, |row, col| spiral.position(row, col) + 1)
+}
+
+/// The Ulam spiral: which cells of [`ulam_numbers`] hold a prime. Primes famously tend to line up
+/// along diagonals.
+///
+/// # Example
+///
+/// ```
+/// use week1::generator::ulam;
+///
+/// // 5 4 3
+/// // 6 1 2
+/// // 7 8 9
+/// let primes = ulam(3, 3).unwrap();
+/// assert_eq!(
+///     primes.into_rows(),
+///     vec![
+///         vec![true, false, true],
+///         vec![false, false, true],
+///         vec![true, false, false],
+///     ]
+/// );
+/// ```
+///
+/// # Approach
+///
+/// Sieve of Eratosthenes up to rows * cols, then look up the number in each cell.
+///
+/// # Time and Space Complexity
+///
+/// O(n log log n) time for the sieve, where n is rows * cols, and O(n) space.
+pub fn ulam(rows: usize, cols: usize) -> Result<Grid<bool>, GeneratorError> {
+    let numbers = ulam_numbers(rows, cols)?;
+    let prime = sieve(rows * cols);
+    generate(rows, cols, |row, col| prime[numbers[(row, col)]])
+}
+
+/// Whether each number up to and including `max` is prime.
+fn sieve(max: usize) -> Vec<bool> {
+    let mut prime = vec![true; max + 1];
+    prime[0] = false;
+    if max >= 1 {
+        prime[1] = false;
+    }
+    let mut n = 2;
+    while n * n <= max {
+        if prime[n] {
+            (n * n..=max)
+                .step_by(n)
+                .for_each(|multiple| prime[multiple] = false);
+        }
+        n += 1;
+    }
+    prime
+}
+
+/// A small, fast, deterministic pseudo-random number generator (SplitMix64), so random matrices
+/// don't need any dependencies and are the same everywhere. It's not meant for anything that needs
+/// to be unpredictable.
+///
+/// # Example
+///
+/// ```
+/// use week1::generator::Rng;
+///
+/// let mut rng = Rng::new(7);
+/// let roll = rng.range(1..7);
+/// assert!((1..7).contains(&roll));
+/// assert_eq!(Rng::new(7).range(1..7), roll);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// A generator that always produces the same numbers for the same seed.
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    /// The next 64 random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A random number below `bound`, with every number equally likely.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bound` is 0.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "there are no numbers below 0");
+        // Lemire's method: scale up to 128 bits, and retry the few values that would make the
+        // low numbers more likely than the high ones
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let wide = self.next_u64() as u128 * bound as u128;
+            if wide as u64 >= threshold {
+                return (wide >> 64) as u64;
+            }
+        }
+    }
+
+    /// A random number in `range`, with every number equally likely.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is empty.
+    pub fn range(&mut self, range: Range<i64>) -> i64 {
+        assert!(!range.is_empty(), "the range {:?} is empty", range);
+        let span = range.end.abs_diff(range.start);
+        range.start.wrapping_add(self.below(span) as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_errors() {
+        assert_eq!(
+            counting(3, 0),
+            Err(GeneratorError::Empty { rows: 3, cols: 0 })
+        );
+        assert_eq!(
+            constant(usize::MAX, 2, 0),
+            Err(GeneratorError::TooBig {
+                rows: usize::MAX,
+                cols: 2
+            })
+        );
+        assert_eq!(
+            random(2, 2, 5..5, 0),
+            Err(GeneratorError::EmptyRange { start: 5, end: 5 })
+        );
+    }
+
+    #[test]
+    fn test_counting_matches_create_matrix() {
+        let matrix = crate::traversal::spiral::create_matrix(4);
+        let counting = counting(4, 4).unwrap();
+        assert_eq!(
+            counting.into_rows(),
+            matrix
+                .iter()
+                .map(|row| row.iter().map(|&x| x as usize).collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_identity() {
+        let matrix = identity::<f64>(3, 3).unwrap();
+        assert_eq!(
+            matrix.into_rows(),
+            vec![
+                vec![1.0, 0.0, 0.0],
+                vec![0.0, 1.0, 0.0],
+                vec![0.0, 0.0, 1.0]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_random_is_seeded() {
+        let a = random(20, 30, -5..5, 1).unwrap();
+        let b = random(20, 30, -5..5, 2).unwrap();
+        assert_eq!(a, random(20, 30, -5..5, 1).unwrap());
+        assert_ne!(a, b);
+        assert!(a.as_slice().iter().all(|x| (-5..5).contains(x)));
+        // with 600 draws, every one of the 10 numbers shows up
+        for n in -5..5 {
+            assert!(a.as_slice().contains(&n), "{n} never came up");
+        }
+    }
+
+    #[test]
+    fn test_rng_known_values() {
+        // the reference SplitMix64 output for seed 0
+        let mut rng = Rng::new(0);
+        assert_eq!(rng.next_u64(), 0xe220_a839_7b1d_cdaf);
+        assert_eq!(rng.next_u64(), 0x6e78_9e6a_a1b9_65f4);
+    }
+
+    #[test]
+    fn test_rng_extremes() {
+        let mut rng = Rng::new(3);
+        for _ in 0..100 {
+            let x = rng.range(i64::MIN..i64::MAX);
+            assert!(x < i64::MAX);
+            assert_eq!(rng.range(-1..0), -1);
+        }
+    }
+
+    #[test]
+    fn test_ulam_numbers() {
+        // 17 16 15 14 13
+        // 18  5  4  3 12
+        // 19  6  1  2 11
+        // 20  7  8  9 10
+        // 21 22 23 24 25
+        let numbers = ulam_numbers(5, 5).unwrap();
+        assert_eq!(numbers.row(0), &[17, 16, 15, 14, 13]);
+        assert_eq!(numbers.row(2), &[19, 6, 1, 2, 11]);
+        assert_eq!(numbers.row(4), &[21, 22, 23, 24, 25]);
+
+        // without a middle cell it still ends in the bottom right corner
+        let numbers = ulam_numbers(2, 4).unwrap();
+        assert_eq!(
+            numbers.into_rows(),
+            vec![vec![4, 3, 2, 1], vec![5, 6, 7, 8]]
+        );
+        let numbers = ulam_numbers(4, 4).unwrap();
+        assert_eq!(numbers.row(3), &[13, 14, 15, 16]);
+    }
+
+    #[test]
+    fn test_ulam_primes() {
+        let primes = ulam(50, 50).unwrap();
+        let numbers = ulam_numbers(50, 50).unwrap();
+        let count = primes.as_slice().iter().filter(|&&p| p).count();
+        // there are 367 primes up to 2500
+        assert_eq!(count, 367);
+        for (&prime, &n) in primes.as_slice().iter().zip(numbers.as_slice()) {
+            assert_eq!(
+                prime,
+                n > 1 && (2..n).take_while(|d| d * d <= n).all(|d| n % d != 0)
+            );
+        }
+    }
+}
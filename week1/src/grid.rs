@@ -0,0 +1,580 @@
+use std::ops::{Index, IndexMut};
+
+use crate::traversal::transform::{self, Angle};
+
+/// A 2D matrix stored as one flat row-major buffer, whose shape is checked once when it's built.
+/// Every row has the same number of columns, and a grid with any rows has at least one column.
+///
+/// # Example
+///
+/// ```
+/// use week1::grid::{Grid, GridError};
+///
+/// let grid = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+/// assert_eq!(grid.dims(), (2, 3));
+/// assert_eq!(grid[(1, 0)], 4);
+/// assert_eq!(grid.row(0), &[1, 2, 3]);
+///
+/// assert_eq!(
+///     Grid::from_rows(vec![vec![1, 2], vec![3]]),
+///     Err(GridError::Ragged { row: 1, expected: 2, actual: 1 })
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    data: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+/// The reasons a [`Grid`] can't be built from some input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridError {
+    /// Row `row` had `actual` columns, but the rows before it had `expected`.
+    Ragged {
+        row: usize,
+        expected: usize,
+        actual: usize,
+    },
+    /// There are rows, but they don't have any columns.
+    ZeroWidth,
+    /// A flat buffer had `actual` elements, but rows * cols is `expected`.
+    LengthMismatch { expected: usize, actual: usize },
+}
+
+impl std::fmt::Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            GridError::Ragged {
+                row,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "row {} has {} columns, but the rows before it have {}",
+                row, actual, expected
+            ),
+            GridError::ZeroWidth => write!(f, "a matrix with rows must have at least one column"),
+            GridError::LengthMismatch { expected, actual } => write!(
+                f,
+                "expected {} elements to fill the matrix, got {}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+impl<T> Grid<T> {
+    /// Builds a grid from a Vec of rows, checking that they all have the same, non-zero length.
+    /// No rows at all is fine, and makes an empty 0x0 grid.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>, GridError> {
+        rows.check_rows()?;
+        let cols = rows.first().map_or(0, Vec::len);
+        if !rows.is_empty() && cols == 0 {
+            return Err(GridError::ZeroWidth);
+        }
+
+        Ok(Grid {
+            rows: rows.len(),
+            cols,
+            data: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Builds a rows x cols grid from a flat row-major buffer, which must have exactly rows * cols
+    /// elements.
+    pub fn from_flat(rows: usize, cols: usize, data: Vec<T>) -> Result<Grid<T>, GridError> {
+        if rows > 0 && cols == 0 {
+            return Err(GridError::ZeroWidth);
+        }
+        if data.len() != rows * cols {
+            return Err(GridError::LengthMismatch {
+                expected: rows * cols,
+                actual: data.len(),
+            });
+        }
+        Ok(Grid { data, rows, cols })
+    }
+
+    /// Builds a rows x cols grid by copying a flat row-major slice, which must have exactly
+    /// rows * cols elements.
+    pub fn from_slice(rows: usize, cols: usize, data: &[T]) -> Result<Grid<T>, GridError>
+    where
+        T: Clone,
+    {
+        Grid::from_flat(rows, cols, data.to_vec())
+    }
+
+    /// Builds a rows x cols grid from the first rows * cols values of an iterator, in row-major
+    /// order. Values after that are left in the iterator, so it can be infinite.
+    pub fn from_iter<I>(rows: usize, cols: usize, values: I) -> Result<Grid<T>, GridError>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let data = values.into_iter().take(rows * cols).collect();
+        Grid::from_flat(rows, cols, data)
+    }
+
+    /// The number of rows.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// The number of columns.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// The (rows, cols) of the grid.
+    pub fn dims(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    /// The number of elements, i.e. rows * cols.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Whether the grid has no elements, i.e. no rows or no columns.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// The element at (row, col), or None if that's outside of the grid.
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        if row < self.rows && col < self.cols {
+            Some(&self.data[row * self.cols + col])
+        } else {
+            None
+        }
+    }
+
+    /// The element at (row, col), or None if that's outside of the grid.
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        if row < self.rows && col < self.cols {
+            Some(&mut self.data[row * self.cols + col])
+        } else {
+            None
+        }
+    }
+
+    /// One row of the grid as a slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` is outside of the grid.
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < self.rows, "row {} is outside of the grid", row);
+        &self.data[row * self.cols..(row + 1) * self.cols]
+    }
+
+    /// One row of the grid as a mutable slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` is outside of the grid.
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        assert!(row < self.rows, "row {} is outside of the grid", row);
+        &mut self.data[row * self.cols..(row + 1) * self.cols]
+    }
+
+    /// The whole grid as a flat row-major slice.
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    /// The whole grid as a flat row-major mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    /// Gives back the flat row-major buffer.
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    /// Transposes the grid in place, so a rows x cols grid becomes cols x rows. See
+    /// [`transpose_flat`](crate::traversal::transform::transpose_flat).
+    pub fn transpose(&mut self) {
+        let dims = transform::transpose_flat(&mut self.data, self.rows, self.cols);
+        self.reshape(dims);
+    }
+
+    /// Rotates the grid clockwise in place. Quarter turns swap the number of rows and columns. See
+    /// [`rotate_flat`](crate::traversal::transform::rotate_flat).
+    pub fn rotate(&mut self, angle: Angle) {
+        let dims = transform::rotate_flat(&mut self.data, self.rows, self.cols, angle);
+        self.reshape(dims);
+    }
+
+    /// Takes on new dims after the buffer has been rearranged. An empty grid with columns but no
+    /// rows would come out with rows but no columns, so it becomes 0x0 instead.
+    fn reshape(&mut self, (rows, cols): (usize, usize)) {
+        (self.rows, self.cols) = if self.data.is_empty() {
+            (0, 0)
+        } else {
+            (rows, cols)
+        };
+    }
+
+    /// Splits the grid back up into a Vec of rows.
+    pub fn into_rows(self) -> Vec<Vec<T>> {
+        let cols = self.cols;
+        let mut data = self.data.into_iter();
+        (0..self.rows)
+            .map(|_| data.by_ref().take(cols).collect())
+            .collect()
+    }
+}
+
+impl<T> TryFrom<Vec<Vec<T>>> for Grid<T> {
+    type Error = GridError;
+
+    fn try_from(rows: Vec<Vec<T>>) -> Result<Grid<T>, GridError> {
+        Grid::from_rows(rows)
+    }
+}
+
+impl<T: Clone> TryFrom<&[Vec<T>]> for Grid<T> {
+    type Error = GridError;
+
+    fn try_from(rows: &[Vec<T>]) -> Result<Grid<T>, GridError> {
+        Grid::from_rows(rows.to_vec())
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        self.get(row, col).unwrap_or_else(|| {
+            panic!(
+                "({}, {}) is outside of a {}x{} grid",
+                row, col, self.rows, self.cols
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        let (rows, cols) = (self.rows, self.cols);
+        self.get_mut(row, col)
+            .unwrap_or_else(|| panic!("({}, {}) is outside of a {}x{} grid", row, col, rows, cols))
+    }
+}
+
+/// A matrix that is laid out as rows of equal length, which can be read a row at a time. This is
+/// implemented for [`Grid`], and for plain nested Vecs, where the number of columns is taken from
+/// the first row, and [`check_rows`](RowMajor::check_rows) makes sure the others agree.
+pub trait RowMajor {
+    type Item;
+
+    /// The (rows, cols) of the matrix.
+    fn dims(&self) -> (usize, usize);
+
+    /// One row of the matrix as a slice.
+    fn row(&self, row: usize) -> &[Self::Item];
+
+    /// Checks that every row is as long as [`dims`](RowMajor::dims) says, which takes O(rows) for
+    /// nested Vecs. Walks over the whole matrix call this once before they start.
+    fn check_rows(&self) -> Result<(), GridError> {
+        let (rows, cols) = self.dims();
+        match (0..rows)
+            .map(|row| self.row(row).len())
+            .enumerate()
+            .find(|&(_, len)| len != cols)
+        {
+            Some((row, actual)) => Err(GridError::Ragged {
+                row,
+                expected: cols,
+                actual,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T> RowMajor for Grid<T> {
+    type Item = T;
+
+    fn dims(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    fn row(&self, row: usize) -> &[T] {
+        Grid::row(self, row)
+    }
+
+    fn check_rows(&self) -> Result<(), GridError> {
+        // the shape was checked when the grid was built
+        Ok(())
+    }
+}
+
+impl<T> RowMajor for [Vec<T>] {
+    type Item = T;
+
+    fn dims(&self) -> (usize, usize) {
+        (self.len(), self.first().map_or(0, Vec::len))
+    }
+
+    fn row(&self, row: usize) -> &[T] {
+        &self[row]
+    }
+}
+
+impl<T> RowMajor for Vec<Vec<T>> {
+    type Item = T;
+
+    fn dims(&self) -> (usize, usize) {
+        self.as_slice().dims()
+    }
+
+    fn row(&self, row: usize) -> &[T] {
+        &self[row]
+    }
+}
+
+impl<T, const N: usize> RowMajor for [Vec<T>; N] {
+    type Item = T;
+
+    fn dims(&self) -> (usize, usize) {
+        self.as_slice().dims()
+    }
+
+    fn row(&self, row: usize) -> &[T] {
+        &self[row]
+    }
+}
+
+/// A [`RowMajor`] matrix whose elements can be changed in place.
+pub trait RowMajorMut: RowMajor {
+    /// One row of the matrix as a mutable slice.
+    fn row_mut(&mut self, row: usize) -> &mut [Self::Item];
+
+    /// Every row of the matrix as a mutable slice, all borrowed at once.
+    fn rows_mut(&mut self) -> Vec<&mut [Self::Item]>;
+
+    /// Swaps the elements at two (row, col) cells, which don't have to be in the same row.
+    fn swap(&mut self, a: (usize, usize), b: (usize, usize));
+}
+
+impl<T> RowMajorMut for Grid<T> {
+    fn row_mut(&mut self, row: usize) -> &mut [T] {
+        Grid::row_mut(self, row)
+    }
+
+    fn rows_mut(&mut self) -> Vec<&mut [T]> {
+        // an empty grid can have no columns, and chunks of 0 make no sense
+        if self.cols == 0 {
+            return Vec::new();
+        }
+        self.data.chunks_mut(self.cols).collect()
+    }
+
+    fn swap(&mut self, a: (usize, usize), b: (usize, usize)) {
+        let index = |(row, col): (usize, usize)| {
+            assert!(
+                row < self.rows && col < self.cols,
+                "({}, {}) is outside of a {}x{} grid",
+                row,
+                col,
+                self.rows,
+                self.cols
+            );
+            row * self.cols + col
+        };
+        let (a, b) = (index(a), index(b));
+        self.data.swap(a, b);
+    }
+}
+
+impl<T> RowMajorMut for [Vec<T>] {
+    fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self[row]
+    }
+
+    fn rows_mut(&mut self) -> Vec<&mut [T]> {
+        self.iter_mut().map(Vec::as_mut_slice).collect()
+    }
+
+    fn swap(&mut self, a: (usize, usize), b: (usize, usize)) {
+        if a.0 == b.0 {
+            self[a.0].swap(a.1, b.1);
+            return;
+        }
+        // split the rows so we can borrow both of them mutably at once
+        let (upper, lower) = if a.0 < b.0 { (a, b) } else { (b, a) };
+        let (top, bottom) = self.split_at_mut(lower.0);
+        std::mem::swap(&mut top[upper.0][upper.1], &mut bottom[0][lower.1]);
+    }
+}
+
+impl<T> RowMajorMut for Vec<Vec<T>> {
+    fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self[row]
+    }
+
+    fn rows_mut(&mut self) -> Vec<&mut [T]> {
+        self.as_mut_slice().rows_mut()
+    }
+
+    fn swap(&mut self, a: (usize, usize), b: (usize, usize)) {
+        RowMajorMut::swap(self.as_mut_slice(), a, b)
+    }
+}
+
+impl<T, const N: usize> RowMajorMut for [Vec<T>; N] {
+    fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self[row]
+    }
+
+    fn rows_mut(&mut self) -> Vec<&mut [T]> {
+        self.as_mut_slice().rows_mut()
+    }
+
+    fn swap(&mut self, a: (usize, usize), b: (usize, usize)) {
+        RowMajorMut::swap(self.as_mut_slice(), a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_from_rows() {
+        let grid = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        assert_eq!(grid.dims(), (2, 3));
+        assert_eq!(grid.len(), 6);
+        assert_eq!(grid.as_slice(), &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid[(0, 2)], 3);
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.into_rows(), vec![vec![1, 2, 3], vec![4, 5, 6]]);
+    }
+
+    #[test]
+    fn test_grid_empty() {
+        let grid = Grid::<i32>::from_rows(vec![]).unwrap();
+        assert_eq!(grid.dims(), (0, 0));
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn test_grid_ragged() {
+        assert_eq!(
+            Grid::from_rows(vec![vec![1, 2], vec![3, 4], vec![5, 6, 7]]),
+            Err(GridError::Ragged {
+                row: 2,
+                expected: 2,
+                actual: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_row_major_check_rows() {
+        let ragged = vec![vec![1, 2], vec![3], vec![4, 5]];
+        assert_eq!(RowMajor::dims(&ragged), (3, 2));
+        assert_eq!(
+            ragged.check_rows(),
+            Err(GridError::Ragged {
+                row: 1,
+                expected: 2,
+                actual: 1
+            })
+        );
+        assert_eq!(vec![vec![1, 2], vec![3, 4]].check_rows(), Ok(()));
+        assert_eq!(Vec::<Vec<i32>>::new().check_rows(), Ok(()));
+    }
+
+    #[test]
+    fn test_grid_zero_width() {
+        assert_eq!(
+            Grid::<i32>::from_rows(vec![vec![]]),
+            Err(GridError::ZeroWidth)
+        );
+        assert_eq!(
+            Grid::<i32>::from_flat(3, 0, vec![]),
+            Err(GridError::ZeroWidth)
+        );
+    }
+
+    #[test]
+    fn test_grid_from_flat() {
+        let grid = Grid::from_flat(2, 2, vec![1, 2, 3, 4]).unwrap();
+        assert_eq!(grid.row(1), &[3, 4]);
+        assert_eq!(
+            Grid::from_flat(2, 2, vec![1, 2, 3]),
+            Err(GridError::LengthMismatch {
+                expected: 4,
+                actual: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_grid_from_slice() {
+        let grid = Grid::from_slice(3, 1, &["a", "b", "c"]).unwrap();
+        assert_eq!(grid[(2, 0)], "c");
+        assert!(Grid::from_slice(2, 2, &[1]).is_err());
+    }
+
+    #[test]
+    fn test_grid_from_iter() {
+        let grid = Grid::from_iter(3, 2, 0..).unwrap();
+        assert_eq!(grid.as_slice(), &[0, 1, 2, 3, 4, 5]);
+        assert_eq!(
+            Grid::from_iter(3, 2, 0..4),
+            Err(GridError::LengthMismatch {
+                expected: 6,
+                actual: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_row_major_swap() {
+        let mut rows = vec![vec![1, 2], vec![3, 4], vec![5, 6]];
+        RowMajorMut::swap(&mut rows, (2, 1), (0, 0));
+        RowMajorMut::swap(&mut rows, (1, 0), (1, 1));
+        assert_eq!(rows, vec![vec![6, 2], vec![4, 3], vec![5, 1]]);
+
+        let mut grid = Grid::from_flat(3, 2, vec![1, 2, 3, 4, 5, 6]).unwrap();
+        RowMajorMut::swap(&mut grid, (2, 1), (0, 0));
+        RowMajorMut::swap(&mut grid, (1, 0), (1, 1));
+        assert_eq!(grid.as_slice(), &[6, 2, 4, 3, 5, 1]);
+    }
+
+    #[test]
+    fn test_row_major_rows_mut() {
+        let mut grid = Grid::from_flat(2, 3, vec![1, 2, 3, 4, 5, 6]).unwrap();
+        let mut rows = grid.rows_mut();
+        rows[0][2] = 30;
+        rows[1][0] = 40;
+        assert_eq!(grid.as_slice(), &[1, 2, 30, 40, 5, 6]);
+
+        let mut nested = vec![vec![1, 2], vec![3, 4]];
+        assert_eq!(nested.rows_mut(), vec![&mut [1, 2][..], &mut [3, 4][..]]);
+        assert!(Vec::<Vec<i32>>::new().rows_mut().is_empty());
+    }
+
+    #[test]
+    fn test_grid_index_mut() {
+        let mut grid = Grid::from_flat(2, 2, vec![0; 4]).unwrap();
+        grid[(1, 0)] = 7;
+        grid.row_mut(0)[1] = 3;
+        assert_eq!(grid.as_slice(), &[0, 3, 7, 0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_grid_index_outside() {
+        let grid = Grid::from_flat(2, 2, vec![0; 4]).unwrap();
+        let _ = grid[(0, 2)];
+    }
+}
@@ -0,0 +1,8 @@
+#![doc(test(attr(deny(warnings))))]
+pub mod employee_count;
+pub mod generator;
+pub mod grid;
+pub mod segregate;
+pub mod traversal;
+pub mod view;
+pub mod wave_sort;
@@ -1,16 +1,37 @@
-mod employee_count;
-mod segregate;
-mod spiral;
-mod wave_sort;
-
-use employee_count::employee_count;
-use segregate::segregate;
-use spiral::{create_matrix, spiral};
 use std::collections::{HashMap, LinkedList};
+use std::env;
+use std::num::NonZeroUsize;
+use std::process;
+use std::thread;
 use std::time::Instant;
-use wave_sort::wave_sort;
+use week1::employee_count::employee_count;
+use week1::segregate::segregate;
+use week1::traversal::parallel::par_spiral;
+use week1::traversal::render::Render;
+use week1::traversal::spiral::{create_matrix, spiral, spiral_fn, SpiralIter};
+use week1::traversal::{Hilbert, Morton, Snake, Spiral, Traversal, Zigzag};
+use week1::view::from_fn;
+use week1::wave_sort::{is_wave, par_wave_sort, wave_sort, WaveKind};
+
+const USAGE: &str = "usage: week1 <spiral|snake|zigzag|hilbert|morton> [--render ascii|svg] [--values] <rows>x<cols>
+
+Draws the path the traversal takes through a rows x cols matrix. --values labels each cell
+with its value in the matrix 0, 1, 2, ... filled in row by row. Without any arguments, runs
+the examples instead.";
 
 fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    if !args.is_empty() {
+        match render_command(&args) {
+            Ok(drawing) => print!("{}", drawing),
+            Err(message) => {
+                eprintln!("{}\n\n{}", message, USAGE);
+                process::exit(2);
+            }
+        }
+        return;
+    }
+
     let spiral1 = spiral(&[
         vec![1, 2, 3, 4],
         vec![5, 6, 7, 8],
@@ -31,6 +52,48 @@ fn main() {
         start.elapsed()
     );
 
+    let start = Instant::now();
+    let giga_sum = SpiralIter::new(&giga_matrix)
+        .map(|&x| x as i64)
+        .sum::<i64>();
+    println!(
+        "   Huge spiral sum without allocating: {:?}, took {:?}. \n",
+        giga_sum,
+        start.elapsed()
+    );
+
+    let start = Instant::now();
+    let virtual_spiral = spiral_fn(1000, 1000, |row, col| (row * 1000 + col) as i32);
+    println!(
+        "   Huge spiral of a matrix that was never built: {:?}, took {:?}. \n",
+        virtual_spiral == giga_spiral,
+        start.elapsed()
+    );
+
+    let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    for size in [1000, 10000] {
+        let matrix = create_matrix(size);
+
+        let start = Instant::now();
+        let sequential = spiral(&matrix);
+        let sequential_time = start.elapsed();
+
+        let start = Instant::now();
+        let parallel = par_spiral(&matrix);
+        let parallel_time = start.elapsed();
+
+        assert_eq!(parallel, sequential);
+        println!(
+            "   Parallel spiral of {}x{} on {} threads: took {:?} vs {:?}, {:.2}x speedup. \n",
+            size,
+            size,
+            threads,
+            parallel_time,
+            sequential_time,
+            sequential_time.as_secs_f64() / parallel_time.as_secs_f64()
+        );
+    }
+
     let nums = [10, 5, 6, 3, 2, 20, 100, 80];
     let mut wave_sorted = nums;
     wave_sort(&mut wave_sorted);
@@ -42,6 +105,32 @@ fn main() {
         nums, wave_sorted
     );
 
+    // a scrambled 10^8 elements, the same every run
+    let huge = (0..100_000_000u32)
+        .map(|i| i.wrapping_mul(2_654_435_761))
+        .collect::<Vec<_>>();
+
+    let mut sequential = huge.clone();
+    let start = Instant::now();
+    wave_sort(&mut sequential);
+    let sequential_time = start.elapsed();
+    drop(sequential);
+
+    let mut parallel = huge.clone();
+    let start = Instant::now();
+    par_wave_sort(&mut parallel);
+    let parallel_time = start.elapsed();
+
+    assert!(is_wave(&parallel, WaveKind::PeakFirst));
+    println!(
+        "   Parallel wave sort of {} elements on {} threads: took {:?} vs {:?}, {:.2}x speedup. \n",
+        parallel.len(),
+        threads,
+        parallel_time,
+        sequential_time,
+        sequential_time.as_secs_f64() / parallel_time.as_secs_f64()
+    );
+
     let mut dict = HashMap::new();
     dict.insert("A", "C");
     dict.insert("B", "C");
@@ -50,12 +139,22 @@ fn main() {
     dict.insert("E", "F");
     dict.insert("F", "F");
 
-    let result = employee_count(&dict);
-
     println!("3. Employee count:");
-    result.iter().for_each(|(k, v)| {
-        println!("  {}: {}", k, v);
-    });
+    match employee_count(&dict) {
+        Ok(result) => result.iter().for_each(|(k, v)| {
+            println!("  {}: {}", k, v);
+        }),
+        Err(error) => println!("  {}", error),
+    }
+
+    dict.insert("C", "A");
+    println!("   After C starts reporting to A:");
+    match employee_count(&dict) {
+        Ok(result) => result.iter().for_each(|(k, v)| {
+            println!("  {}: {}", k, v);
+        }),
+        Err(error) => println!("  {}", error),
+    }
 
     let list = [1, 2, 2, 1, 2, 0, 2, 2];
     let mut linked_list = LinkedList::new();
@@ -81,3 +180,50 @@ fn main() {
         linked_list.iter().collect::<Vec<_>>()
     );
 }
+
+/// Parses the arguments of `week1 <order> --render <format> <rows>x<cols>` and draws the path.
+fn render_command(args: &[String]) -> Result<String, String> {
+    let (mut order, mut shape) = (None, None);
+    let mut format = "ascii";
+    let mut values = false;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--render" => format = args.next().ok_or("--render needs a format")?,
+            "--values" => values = true,
+            _ if order.is_none() => order = Some(arg.as_str()),
+            _ if shape.is_none() => shape = Some(arg.as_str()),
+            _ => return Err(format!("unexpected argument {}", arg)),
+        }
+    }
+
+    let shape = shape.ok_or("missing the shape of the matrix, like 5x7")?;
+    let (rows, cols) = shape
+        .split_once('x')
+        .and_then(|(rows, cols)| Some((rows.parse().ok()?, cols.parse().ok()?)))
+        .ok_or(format!("{} is not a shape like 5x7", shape))?;
+
+    match order {
+        Some("spiral") => render(Spiral::new(rows, cols), format, values),
+        Some("snake") => render(Snake::new(rows, cols), format, values),
+        Some("zigzag") => render(Zigzag::new(rows, cols), format, values),
+        Some("hilbert") => render(Hilbert::new(rows, cols), format, values),
+        Some("morton") => render(Morton::new(rows, cols), format, values),
+        Some(other) => Err(format!("unknown traversal {}", other)),
+        None => Err("missing the traversal".to_string()),
+    }
+}
+
+fn render<O: Traversal>(traversal: O, format: &str, values: bool) -> Result<String, String> {
+    let (rows, cols) = traversal.dims();
+    let mut render = Render::new(traversal);
+    if values {
+        render = render.labels(&from_fn(rows, cols, |row, col| row * cols + col));
+    }
+    match format {
+        "ascii" => Ok(render.ascii()),
+        "svg" => Ok(render.svg()),
+        other => Err(format!("unknown format {}, use ascii or svg", other)),
+    }
+}
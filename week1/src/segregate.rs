@@ -12,6 +12,10 @@ use std::collections::LinkedList;
 /// # Examples
 ///
 /// ```
+/// use std::collections::LinkedList;
+///
+/// use week1::segregate::segregate;
+///
 /// // [1, 2, 2, 1, 2, 0, 2, 2] -> [0, 1, 1, 2, 2, 2, 2, 2]
 /// let mut list = LinkedList::new();
 /// [1, 2, 2, 1, 2, 0, 2, 2]
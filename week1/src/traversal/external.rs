@@ -0,0 +1,301 @@
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+use super::rings::{ring_count, ring_len};
+use super::spiral::spiral_position;
+
+/// Spirals a matrix that's too big for memory: reads it from a row-major binary file (or anything
+/// else that's [`Read`] + [`Seek`]) of fixed-width elements, and streams the elements out to a
+/// [`Write`] sink in the same order as [`spiral`](super::spiral::spiral).
+///
+/// Elements are just `elem_size` bytes each and are copied over as they are, so this works for any
+/// fixed-width type in any byte order. Element (row, col) is expected at byte
+/// (row * cols + col) * elem_size of the source.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+///
+/// use week1::traversal::external::ExternalSpiral;
+///
+/// // a 2x3 matrix of u16s
+/// let bytes = [1u16, 2, 3, 4, 5, 6]
+///     .iter()
+///     .flat_map(|x| x.to_le_bytes())
+///     .collect::<Vec<_>>();
+///
+/// let mut out = Vec::new();
+/// ExternalSpiral::new(2, 3, 2)
+///     .run(&mut Cursor::new(bytes), &mut out)
+///     .unwrap();
+///
+/// let spiral = out
+///     .chunks(2)
+///     .map(|x| u16::from_le_bytes([x[0], x[1]]))
+///     .collect::<Vec<_>>();
+/// assert_eq!(spiral, vec![1, 2, 3, 6, 5, 4]);
+/// ```
+///
+/// # Approach
+///
+/// The spiral walks the columns at the sides of each ring one element per row, so reading it in
+/// order would mean a seek for almost every element. Instead, the rings are split into groups that
+/// fit in a buffer of `buffer_size` bytes. The elements of a group of rings are one contiguous
+/// chunk of the spiral, so for each group we sweep once down the rows the group covers, read the
+/// parts of each row that belong to the group (the whole row if it's the top or bottom of one of
+/// its rings, otherwise just the strips of columns on the left and right), and drop every element
+/// straight into its place in the chunk with [`spiral_position`]. Then the chunk is written out in
+/// one go.
+///
+/// # Time and Space Complexity
+///
+/// Every element is read exactly once and written exactly once, so it's O(n) bytes of I/O. Each
+/// row is read (in at most two reads) once per group, and there are about
+/// rows * cols * elem_size / buffer_size groups. The memory used is the buffer, which always holds
+/// at least one whole ring, plus one row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExternalSpiral {
+    rows: usize,
+    cols: usize,
+    elem_size: usize,
+    buffer_size: usize,
+}
+
+impl ExternalSpiral {
+    /// How much of the spiral is buffered at once, unless told otherwise.
+    pub const DEFAULT_BUFFER_SIZE: usize = 64 * 1024 * 1024;
+
+    /// A spiral over a rows x cols matrix of `elem_size` byte elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `elem_size` is 0.
+    pub fn new(rows: usize, cols: usize, elem_size: usize) -> ExternalSpiral {
+        assert!(elem_size > 0, "elements must be at least one byte");
+        ExternalSpiral {
+            rows,
+            cols,
+            elem_size,
+            buffer_size: ExternalSpiral::DEFAULT_BUFFER_SIZE,
+        }
+    }
+
+    /// Sets roughly how many bytes of the spiral to hold in memory at once. Bigger buffers mean
+    /// fewer sweeps over the source.
+    pub fn buffer_size(mut self, bytes: usize) -> ExternalSpiral {
+        self.buffer_size = bytes;
+        self
+    }
+
+    /// Reads the matrix from `source` and writes its spiral to `sink`.
+    ///
+    /// # Errors
+    ///
+    /// Any error from reading, seeking or writing, including an
+    /// [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) if the source is too short for the matrix.
+    pub fn run<R, W>(&self, source: &mut R, sink: &mut W) -> io::Result<()>
+    where
+        R: Read + Seek + ?Sized,
+        W: Write + ?Sized,
+    {
+        let (rows, cols, size) = (self.rows, self.cols, self.elem_size);
+        let rings = ring_count(rows, cols);
+
+        let mut row_buf = vec![0; cols * size];
+        let mut chunk = Vec::new();
+        let mut first = 0;
+        while first < rings {
+            let last = self.group_end(first, rings);
+            let start = spiral_position(rows, cols, first, first);
+            let end = if last < rings {
+                spiral_position(rows, cols, last, last)
+            } else {
+                rows * cols
+            };
+
+            chunk.resize((end - start) * size, 0);
+            for row in first..rows - first {
+                for (from, to) in self.segments(row, first, last) {
+                    let bytes = &mut row_buf[..(to - from) * size];
+                    source.seek(SeekFrom::Start(((row * cols + from) * size) as u64))?;
+                    source.read_exact(bytes)?;
+
+                    for (col, elem) in (from..to).zip(bytes.chunks_exact(size)) {
+                        let pos = spiral_position(rows, cols, row, col) - start;
+                        chunk[pos * size..(pos + 1) * size].copy_from_slice(elem);
+                    }
+                }
+            }
+            sink.write_all(&chunk)?;
+
+            first = last;
+        }
+        sink.flush()
+    }
+
+    /// The ring after the last one of the group that starts at ring `first`: as many rings as fit
+    /// in the buffer, but always at least one.
+    fn group_end(&self, first: usize, rings: usize) -> usize {
+        let mut bytes = ring_len(self.rows, self.cols, first) * self.elem_size;
+        let mut last = first + 1;
+        while last < rings {
+            let more = ring_len(self.rows, self.cols, last) * self.elem_size;
+            if bytes + more > self.buffer_size {
+                break;
+            }
+            bytes += more;
+            last += 1;
+        }
+        last
+    }
+
+    /// The ranges of columns of `row` whose elements are on rings first..last.
+    fn segments(&self, row: usize, first: usize, last: usize) -> Vec<(usize, usize)> {
+        let (rows, cols) = (self.rows, self.cols);
+        let row_ring = row.min(rows - 1 - row);
+        if row_ring < last || 2 * last >= cols {
+            // either the top or bottom row of one of the rings, or the strips meet in the middle
+            vec![(first, cols - first)]
+        } else {
+            vec![(first, last), (cols - last, cols - first)]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traversal::numbered;
+    use crate::traversal::spiral::spiral;
+    use std::fs::{self, File};
+    use std::io::{BufWriter, Cursor};
+
+    /// A reader that counts how many reads and bytes go through it.
+    struct Counting<R> {
+        inner: R,
+        reads: usize,
+        bytes: usize,
+    }
+
+    impl<R: Read> Read for Counting<R> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let read = self.inner.read(buf)?;
+            self.reads += 1;
+            self.bytes += read;
+            Ok(read)
+        }
+    }
+
+    impl<R: Seek> Seek for Counting<R> {
+        fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    fn to_bytes(matrix: &[Vec<usize>]) -> Vec<u8> {
+        matrix
+            .iter()
+            .flatten()
+            .flat_map(|&x| (x as u32).to_le_bytes())
+            .collect()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Vec<usize> {
+        bytes
+            .chunks_exact(4)
+            .map(|x| u32::from_le_bytes([x[0], x[1], x[2], x[3]]) as usize)
+            .collect()
+    }
+
+    #[test]
+    fn test_external_spiral_matches_spiral() {
+        for rows in 0..=9 {
+            for cols in 0..=9 {
+                let matrix = numbered(rows, cols);
+                // from one ring per group up to everything at once
+                for buffer_size in [0, 16, 40, 100, 1 << 20] {
+                    let mut out = Vec::new();
+                    ExternalSpiral::new(rows, cols, 4)
+                        .buffer_size(buffer_size)
+                        .run(&mut Cursor::new(to_bytes(&matrix)), &mut out)
+                        .unwrap();
+                    assert_eq!(
+                        from_bytes(&out),
+                        spiral(&matrix),
+                        "{rows}x{cols}, {buffer_size} bytes"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_external_spiral_file() {
+        let (rows, cols) = (150, 97);
+        let matrix = numbered(rows, cols);
+
+        let dir = std::env::temp_dir();
+        let input = dir.join(format!("week1-external-spiral-{}.in", std::process::id()));
+        let output = dir.join(format!("week1-external-spiral-{}.out", std::process::id()));
+        fs::write(&input, to_bytes(&matrix)).unwrap();
+
+        let mut sink = BufWriter::new(File::create(&output).unwrap());
+        ExternalSpiral::new(rows, cols, 4)
+            .buffer_size(8 * 1024)
+            .run(&mut File::open(&input).unwrap(), &mut sink)
+            .unwrap();
+        drop(sink);
+
+        let result = from_bytes(&fs::read(&output).unwrap());
+        fs::remove_file(&input).unwrap();
+        fs::remove_file(&output).unwrap();
+        assert_eq!(result, spiral(&matrix));
+    }
+
+    #[test]
+    fn test_external_spiral_bounded_reads() {
+        let (rows, cols) = (60, 80);
+        let bytes = to_bytes(&numbered(rows, cols));
+
+        // with room for everything, every element is read exactly once, one row at a time
+        let mut source = Counting {
+            inner: Cursor::new(bytes.clone()),
+            reads: 0,
+            bytes: 0,
+        };
+        ExternalSpiral::new(rows, cols, 4)
+            .run(&mut source, &mut io::sink())
+            .unwrap();
+        assert_eq!(source.bytes, bytes.len());
+        assert_eq!(source.reads, rows);
+
+        // with a small buffer, each group of rings costs at most two reads per row
+        let spiral = ExternalSpiral::new(rows, cols, 4).buffer_size(4 * 1024);
+        let mut source = Counting {
+            inner: Cursor::new(bytes.clone()),
+            reads: 0,
+            bytes: 0,
+        };
+        spiral.run(&mut source, &mut io::sink()).unwrap();
+
+        let rings = ring_count(rows, cols);
+        let mut groups = 0;
+        let mut first = 0;
+        while first < rings {
+            first = spiral.group_end(first, rings);
+            groups += 1;
+        }
+        assert!(groups > 1);
+        assert!(source.reads <= 2 * rows * groups);
+        assert_eq!(source.bytes, bytes.len());
+    }
+
+    #[test]
+    fn test_external_spiral_short_source() {
+        let bytes = to_bytes(&numbered(3, 3));
+        let error = ExternalSpiral::new(4, 3, 4)
+            .run(&mut Cursor::new(bytes), &mut Vec::new())
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
@@ -0,0 +1,301 @@
+use super::Traversal;
+
+/// Walks a matrix along a Hilbert curve: a path that only ever steps to a neighbouring cell, and
+/// fills the matrix a block at a time, so cells that are close together in the order are close
+/// together in the matrix (even more so than [`Morton`](super::Morton) order).
+///
+/// On a power of two square this is the classic Hilbert curve. Other rectangles use the
+/// generalized Hilbert curve (Jakub Červený's "gilbert"), which keeps the same recursive shape and
+/// still only steps between neighbouring cells, except for a single diagonal step on shapes whose
+/// longer side is odd and shorter side is even.
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::{Hilbert, Traversal};
+///
+/// // 0  1 14 15
+/// // 3  2 13 12
+/// // 4  7  8 11
+/// // 5  6  9 10
+/// let hilbert = Hilbert::new(4, 4);
+/// assert_eq!(hilbert.coord(7), (2, 1));
+/// assert_eq!(hilbert.position(3, 0), 5);
+/// ```
+///
+/// # Approach
+///
+/// A block of the curve is a rectangle with a corner to start from, a major axis to travel along
+/// and a minor axis. If the block is a single row or column, the curve just walks along it.
+/// Otherwise it's split into sub-blocks: two halves along the major axis if it's much longer than
+/// it is wide, otherwise three pieces that go up, across and back down, with their axes turned so
+/// that each one ends next to where the following one starts. Both `coord` and `position` go down
+/// the splits to the single row or column that holds the step or cell, adding up the sizes of the
+/// sub-blocks they skip over.
+///
+/// # Time and Space Complexity
+///
+/// `coord` and `position` are O(log(rows * cols)) time and O(1) space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hilbert {
+    rows: usize,
+    cols: usize,
+}
+
+impl Hilbert {
+    /// The Hilbert curve order of a rows x cols matrix.
+    pub fn new(rows: usize, cols: usize) -> Hilbert {
+        Hilbert { rows, cols }
+    }
+
+    /// The whole matrix as one block, travelling along its longer side.
+    fn root(&self) -> Block {
+        let (rows, cols) = (self.rows as i64, self.cols as i64);
+        if cols >= rows {
+            Block::new((0, 0), (cols, 0), (0, rows))
+        } else {
+            Block::new((0, 0), (0, rows), (cols, 0))
+        }
+    }
+}
+
+impl Traversal for Hilbert {
+    fn dims(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    fn coord(&self, pos: usize) -> (usize, usize) {
+        assert!(
+            pos < self.len(),
+            "position {} is outside of the matrix",
+            pos
+        );
+
+        let mut block = self.root();
+        let mut pos = pos as i64;
+        loop {
+            if let Some((x, y)) = block.line_cell(pos) {
+                return (y as usize, x as usize);
+            }
+            let (parts, count) = block.split();
+            for part in &parts[..count] {
+                if pos < part.len() {
+                    block = *part;
+                    break;
+                }
+                pos -= part.len();
+            }
+        }
+    }
+
+    fn position(&self, row: usize, col: usize) -> usize {
+        assert!(
+            row < self.rows && col < self.cols,
+            "({}, {}) is outside of the matrix",
+            row,
+            col
+        );
+
+        let (x, y) = (col as i64, row as i64);
+        let mut block = self.root();
+        let mut pos = 0;
+        loop {
+            let (along, across) = block.offsets(x, y);
+            if block.width() == 1 || block.height() == 1 {
+                return (pos + along + across) as usize;
+            }
+            let (parts, count) = block.split();
+            for part in &parts[..count] {
+                if part.contains(x, y) {
+                    block = *part;
+                    break;
+                }
+                pos += part.len();
+            }
+        }
+    }
+}
+
+/// A rectangle of the curve in (x, y) = (col, row) coordinates: it starts at `start`, and covers
+/// `major` along the axis it travels and `minor` along the other one. The signs of the vectors say
+/// which way it goes.
+#[derive(Debug, Clone, Copy)]
+struct Block {
+    start: (i64, i64),
+    major: (i64, i64),
+    minor: (i64, i64),
+}
+
+impl Block {
+    fn new(start: (i64, i64), major: (i64, i64), minor: (i64, i64)) -> Block {
+        Block {
+            start,
+            major,
+            minor,
+        }
+    }
+
+    /// The length along the major axis. Only one of the components is ever non-zero.
+    fn width(&self) -> i64 {
+        (self.major.0 + self.major.1).abs()
+    }
+
+    /// The length along the minor axis.
+    fn height(&self) -> i64 {
+        (self.minor.0 + self.minor.1).abs()
+    }
+
+    fn len(&self) -> i64 {
+        self.width() * self.height()
+    }
+
+    /// How far (x, y) is from the start of the block along the major and minor axes. Negative or
+    /// too far if it's outside of the block.
+    fn offsets(&self, x: i64, y: i64) -> (i64, i64) {
+        let (dx, dy) = (x - self.start.0, y - self.start.1);
+        let (major, minor) = (self.major, self.minor);
+        (
+            dx * major.0.signum() + dy * major.1.signum(),
+            dx * minor.0.signum() + dy * minor.1.signum(),
+        )
+    }
+
+    fn contains(&self, x: i64, y: i64) -> bool {
+        let (along, across) = self.offsets(x, y);
+        (0..self.width()).contains(&along) && (0..self.height()).contains(&across)
+    }
+
+    /// If the block is a single row or column, the cell `pos` steps along it.
+    fn line_cell(&self, pos: i64) -> Option<(i64, i64)> {
+        let (x, y) = self.start;
+        let step = if self.height() == 1 {
+            self.major
+        } else if self.width() == 1 {
+            self.minor
+        } else {
+            return None;
+        };
+        Some((x + step.0.signum() * pos, y + step.1.signum() * pos))
+    }
+
+    /// Splits the block into the sub-blocks the curve walks through, in order. Only the first
+    /// `count` of them are used. The block must be at least 2x2.
+    fn split(&self) -> ([Block; 3], usize) {
+        let (x, y) = self.start;
+        let (ax, ay) = self.major;
+        let (bx, by) = self.minor;
+        let (w, h) = (self.width(), self.height());
+        let (dax, day) = (ax.signum(), ay.signum());
+        let (dbx, dby) = (bx.signum(), by.signum());
+
+        // rounding down, even for negative vectors, is what keeps the pieces lined up
+        let (mut ax2, mut ay2) = (ax.div_euclid(2), ay.div_euclid(2));
+        let (mut bx2, mut by2) = (bx.div_euclid(2), by.div_euclid(2));
+
+        if 2 * w > 3 * h {
+            // much longer than it is wide: just cut it in half along the major axis, making the
+            // first half even so the curve comes out at the right corner
+            if (ax2 + ay2).abs() % 2 == 1 && w > 2 {
+                (ax2, ay2) = (ax2 + dax, ay2 + day);
+            }
+            let first = Block::new((x, y), (ax2, ay2), (bx, by));
+            let second = Block::new((x + ax2, y + ay2), (ax - ax2, ay - ay2), (bx, by));
+            ([first, second, second], 2)
+        } else {
+            // up the first half of the minor axis, across the whole major axis, and back down
+            if (bx2 + by2).abs() % 2 == 1 && h > 2 {
+                (bx2, by2) = (bx2 + dbx, by2 + dby);
+            }
+            let up = Block::new((x, y), (bx2, by2), (ax2, ay2));
+            let across = Block::new((x + bx2, y + by2), (ax, ay), (bx - bx2, by - by2));
+            let down = Block::new(
+                (x + (ax - dax) + (bx2 - dbx), y + (ay - day) + (by2 - dby)),
+                (-bx2, -by2),
+                (-(ax - ax2), -(ay - ay2)),
+            );
+            ([up, across, down], 3)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traversal::check_traversal;
+
+    /// The classic Hilbert curve on an n x n square, n a power of two, from Wikipedia's d2xy.
+    fn classic(n: usize, mut d: usize) -> (usize, usize) {
+        let (mut x, mut y) = (0, 0);
+        let mut s = 1;
+        while s < n {
+            let rx = 1 & (d / 2);
+            let ry = 1 & (d ^ rx);
+            if ry == 0 {
+                if rx == 1 {
+                    x = s - 1 - x;
+                    y = s - 1 - y;
+                }
+                std::mem::swap(&mut x, &mut y);
+            }
+            x += s * rx;
+            y += s * ry;
+            d /= 4;
+            s *= 2;
+        }
+        (y, x)
+    }
+
+    #[test]
+    fn test_hilbert_power_of_two_is_classic() {
+        for n in [1, 2, 4, 8, 16] {
+            let hilbert = Hilbert::new(n, n);
+            for pos in 0..n * n {
+                assert_eq!(hilbert.coord(pos), classic(n, pos), "{n}x{n}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_hilbert_steps_to_neighbours() {
+        for rows in 1..=16 {
+            for cols in 1..=16 {
+                let coords = Hilbert::new(rows, cols).coords().collect::<Vec<_>>();
+                let diagonals = coords
+                    .windows(2)
+                    .filter(|w| w[0].0.abs_diff(w[1].0) + w[0].1.abs_diff(w[1].1) != 1)
+                    .inspect(|w| {
+                        // the odd diagonal step is still a step to a touching cell
+                        assert_eq!(w[0].0.abs_diff(w[1].0), 1, "{rows}x{cols}");
+                        assert_eq!(w[0].1.abs_diff(w[1].1), 1, "{rows}x{cols}");
+                    })
+                    .count();
+                assert!(diagonals <= 1, "{rows}x{cols}");
+                let (short, long) = (rows.min(cols), rows.max(cols));
+                if short % 2 == 1 || long % 2 == 0 {
+                    // only an even short side with an odd long side ever needs the diagonal
+                    assert_eq!(diagonals, 0, "{rows}x{cols}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_hilbert_rectangle() {
+        // 0 1 2 3 ... along the single row
+        let hilbert = Hilbert::new(1, 5);
+        assert_eq!(
+            hilbert.coords().collect::<Vec<_>>(),
+            (0..5).map(|c| (0, c)).collect::<Vec<_>>()
+        );
+
+        let hilbert = Hilbert::new(4, 2);
+        assert_eq!(hilbert.coord(0), (0, 0));
+        // it travels along the longer side, so it ends at the bottom
+        assert_eq!(hilbert.coord(7), (3, 0));
+    }
+
+    #[test]
+    fn test_hilbert_is_traversal() {
+        check_traversal(12, Hilbert::new);
+    }
+}
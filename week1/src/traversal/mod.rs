@@ -0,0 +1,231 @@
+pub mod external;
+pub mod hilbert;
+pub mod morton;
+pub mod parallel;
+pub mod render;
+pub mod rings;
+pub mod snake;
+pub mod spiral;
+pub mod spiral3d;
+pub mod transform;
+pub mod zigzag;
+
+pub use hilbert::Hilbert;
+pub use morton::Morton;
+pub use snake::Snake;
+pub use spiral::Spiral;
+pub use zigzag::Zigzag;
+
+use crate::grid::{Grid, GridError, RowMajor};
+
+/// An order to visit every cell of a rows x cols matrix exactly once, like [`spiral::spiral`]
+/// does. Every traversal knows both which cell comes at each step (`coord`) and at which step each
+/// cell comes (`position`), so anything walked in one of these orders can be put back together.
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::{Snake, Traversal};
+///
+/// let matrix = vec![vec![1, 2, 3], vec![4, 5, 6]];
+///
+/// let snake = Snake::new(2, 3);
+/// assert_eq!(snake.traverse(&matrix), vec![1, 2, 3, 6, 5, 4]);
+/// assert_eq!(snake.coord(3), (1, 2));
+/// assert_eq!(snake.position(1, 2), 3);
+///
+/// let grid = snake.untraverse(&[1, 2, 3, 6, 5, 4]).unwrap();
+/// assert_eq!(grid.into_rows(), matrix);
+/// ```
+pub trait Traversal: Copy {
+    /// The (rows, cols) of the matrix this walks.
+    fn dims(&self) -> (usize, usize);
+
+    /// The (row, col) of the cell visited at step `pos`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pos` is not less than rows * cols.
+    fn coord(&self, pos: usize) -> (usize, usize);
+
+    /// The step at which the cell at (row, col) is visited. The inverse of `coord`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if (row, col) is outside of the matrix.
+    fn position(&self, row: usize, col: usize) -> usize;
+
+    /// The number of steps, i.e. rows * cols.
+    fn len(&self) -> usize {
+        let (rows, cols) = self.dims();
+        rows * cols
+    }
+
+    /// Whether there are no steps at all, i.e. the matrix has no rows or no columns.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The (row, col) of every cell, in order.
+    fn coords(&self) -> Coords<Self> {
+        Coords {
+            traversal: *self,
+            front: 0,
+            back: self.len(),
+        }
+    }
+
+    /// Lazily walks the elements of `matrix` in this order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `matrix` isn't the shape this traversal was made for.
+    fn iter<'a, M: RowMajor + ?Sized>(&self, matrix: &'a M) -> TraversalIter<'a, Self, M> {
+        assert_eq!(
+            matrix.dims(),
+            self.dims(),
+            "the matrix is not the shape of the traversal"
+        );
+        TraversalIter {
+            matrix,
+            coords: self.coords(),
+        }
+    }
+
+    /// Collects the elements of `matrix` in this order into a Vec.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `matrix` isn't the shape this traversal was made for.
+    fn traverse<M>(&self, matrix: &M) -> Vec<M::Item>
+    where
+        M: RowMajor + ?Sized,
+        M::Item: Copy,
+    {
+        self.iter(matrix).copied().collect()
+    }
+
+    /// The inverse of `traverse`: rebuilds the matrix from its elements in this order. Every cell
+    /// looks up its own element with `position`, so the grid is built in one row-major pass.
+    fn untraverse<T: Clone>(&self, seq: &[T]) -> Result<Grid<T>, GridError> {
+        let (rows, cols) = self.dims();
+        if seq.len() != rows * cols {
+            return Err(GridError::LengthMismatch {
+                expected: rows * cols,
+                actual: seq.len(),
+            });
+        }
+        let values = (0..rows)
+            .flat_map(|row| (0..cols).map(move |col| (row, col)))
+            .map(|(row, col)| seq[self.position(row, col)].clone());
+        Grid::from_iter(rows, cols, values)
+    }
+}
+
+/// The (row, col) of every cell of a matrix in the order of a [`Traversal`].
+#[derive(Debug, Clone)]
+pub struct Coords<O> {
+    traversal: O,
+    front: usize,
+    back: usize,
+}
+
+impl<O: Traversal> Iterator for Coords<O> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.traversal.coord(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.back - self.front;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<O: Traversal> DoubleEndedIterator for Coords<O> {
+    fn next_back(&mut self) -> Option<(usize, usize)> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.traversal.coord(self.back))
+    }
+}
+
+impl<O: Traversal> ExactSizeIterator for Coords<O> {}
+
+/// The elements of a matrix in the order of a [`Traversal`]. See [`Traversal::iter`].
+pub struct TraversalIter<'a, O, M: ?Sized> {
+    matrix: &'a M,
+    coords: Coords<O>,
+}
+
+impl<'a, O: Traversal, M: RowMajor + ?Sized> Iterator for TraversalIter<'a, O, M> {
+    type Item = &'a M::Item;
+
+    fn next(&mut self) -> Option<&'a M::Item> {
+        let (row, col) = self.coords.next()?;
+        Some(&self.matrix.row(row)[col])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.coords.size_hint()
+    }
+}
+
+impl<'a, O: Traversal, M: RowMajor + ?Sized> DoubleEndedIterator for TraversalIter<'a, O, M> {
+    fn next_back(&mut self) -> Option<&'a M::Item> {
+        let (row, col) = self.coords.next_back()?;
+        Some(&self.matrix.row(row)[col])
+    }
+}
+
+impl<'a, O: Traversal, M: RowMajor + ?Sized> ExactSizeIterator for TraversalIter<'a, O, M> {}
+
+/// A rows x cols matrix numbered 0, 1, 2, ... in row-major order.
+#[cfg(test)]
+fn numbered(rows: usize, cols: usize) -> Vec<Vec<usize>> {
+    (0..rows)
+        .map(|row| (0..cols).map(|col| row * cols + col).collect())
+        .collect()
+}
+
+/// Checks the parts of the [`Traversal`] contract that every order shares, on every shape up to
+/// `max` x `max`: every cell is visited exactly once, `position` undoes `coord`, and walking a
+/// matrix then putting it back together gives the same matrix.
+#[cfg(test)]
+fn check_traversal<O: Traversal>(max: usize, new: impl Fn(usize, usize) -> O) {
+    for rows in 0..=max {
+        for cols in 0..=max {
+            let traversal = new(rows, cols);
+            assert_eq!(traversal.dims(), (rows, cols));
+
+            let mut seen = vec![vec![false; cols]; rows];
+            for (pos, (row, col)) in traversal.coords().enumerate() {
+                assert!(
+                    !seen[row][col],
+                    "{rows}x{cols}: ({row}, {col}) visited twice"
+                );
+                seen[row][col] = true;
+                assert_eq!(traversal.position(row, col), pos, "{rows}x{cols}");
+            }
+            assert!(seen.iter().flatten().all(|&x| x), "{rows}x{cols}");
+
+            let mut backwards = traversal.coords().rev().collect::<Vec<_>>();
+            backwards.reverse();
+            assert_eq!(backwards, traversal.coords().collect::<Vec<_>>());
+
+            if rows > 0 && cols > 0 {
+                let matrix = numbered(rows, cols);
+                let seq = traversal.traverse(&matrix);
+                let grid = traversal.untraverse(&seq).unwrap();
+                assert_eq!(grid.into_rows(), matrix, "{rows}x{cols}");
+            }
+        }
+    }
+}
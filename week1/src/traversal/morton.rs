@@ -0,0 +1,165 @@
+use super::Traversal;
+
+/// Walks a matrix in Z-order (Morton order): split the matrix into four quadrants, walk the top
+/// left, top right, bottom left and bottom right quadrants in that order, each of them in Z-order
+/// too. A cell's step is its row and column with their bits interleaved, which keeps cells that
+/// are close together in the matrix close together in the order.
+///
+/// Matrices that aren't a power of two square are treated as the top left corner of the smallest
+/// one that fits, with the cells that fall outside of the matrix skipped.
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::{Morton, Traversal};
+///
+/// //  0  1  4  5
+/// //  2  3  6  7
+/// //  8  9 12 13
+/// // 10 11 14 15
+/// let morton = Morton::new(4, 4);
+/// assert_eq!(morton.position(1, 2), 6);
+/// assert_eq!(morton.coord(9), (2, 1));
+/// ```
+///
+/// # Approach
+///
+/// For a full power of two square, `coord` and `position` just (de)interleave bits. Skipping cells
+/// outside of the matrix means counting how many cells are actually in each quadrant, so instead
+/// we go down the quadrants one level at a time, which takes one step per bit.
+///
+/// # Time and Space Complexity
+///
+/// `coord` and `position` are O(log(max(rows, cols))) time and O(1) space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Morton {
+    rows: usize,
+    cols: usize,
+}
+
+impl Morton {
+    /// The Z-order of a rows x cols matrix.
+    pub fn new(rows: usize, cols: usize) -> Morton {
+        Morton { rows, cols }
+    }
+
+    /// The side of the smallest power of two square that fits the matrix.
+    fn side(&self) -> usize {
+        self.rows.max(self.cols).next_power_of_two()
+    }
+
+    /// The number of cells of the matrix in the size x size block with its top left at (row, col).
+    fn cells_in(&self, row: usize, col: usize, size: usize) -> usize {
+        self.rows.saturating_sub(row).min(size) * self.cols.saturating_sub(col).min(size)
+    }
+}
+
+impl Traversal for Morton {
+    fn dims(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    fn coord(&self, pos: usize) -> (usize, usize) {
+        assert!(
+            pos < self.len(),
+            "position {} is outside of the matrix",
+            pos
+        );
+
+        let (mut row, mut col, mut pos) = (0, 0, pos);
+        let mut size = self.side();
+        while size > 1 {
+            size /= 2;
+            // top left, top right, bottom left, bottom right
+            for (r, c) in [
+                (row, col),
+                (row, col + size),
+                (row + size, col),
+                (row + size, col + size),
+            ] {
+                let cells = self.cells_in(r, c, size);
+                if pos < cells {
+                    (row, col) = (r, c);
+                    break;
+                }
+                pos -= cells;
+            }
+        }
+        (row, col)
+    }
+
+    fn position(&self, row: usize, col: usize) -> usize {
+        assert!(
+            row < self.rows && col < self.cols,
+            "({}, {}) is outside of the matrix",
+            row,
+            col
+        );
+
+        let (mut top, mut left, mut pos) = (0, 0, 0);
+        let mut size = self.side();
+        while size > 1 {
+            size /= 2;
+            for (r, c) in [
+                (top, left),
+                (top, left + size),
+                (top + size, left),
+                (top + size, left + size),
+            ] {
+                if (r..r + size).contains(&row) && (c..c + size).contains(&col) {
+                    (top, left) = (r, c);
+                    break;
+                }
+                pos += self.cells_in(r, c, size);
+            }
+        }
+        pos
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traversal::check_traversal;
+
+    /// Interleaves the bits of row and col, with the row bits in the odd places.
+    fn interleave(row: usize, col: usize) -> usize {
+        (0..usize::BITS as usize / 2)
+            .map(|bit| ((row >> bit & 1) << (2 * bit + 1)) | ((col >> bit & 1) << (2 * bit)))
+            .sum()
+    }
+
+    #[test]
+    fn test_morton_square_is_interleaved_bits() {
+        let morton = Morton::new(16, 16);
+        for row in 0..16 {
+            for col in 0..16 {
+                assert_eq!(morton.position(row, col), interleave(row, col));
+            }
+        }
+    }
+
+    #[test]
+    fn test_morton_rectangle() {
+        // 0 1 4
+        // 2 3 5
+        let matrix = vec![vec![0, 1, 4], vec![2, 3, 5]];
+        assert_eq!(Morton::new(2, 3).traverse(&matrix), vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_morton_keeps_relative_order() {
+        // skipping cells outside of the matrix doesn't change the order of the ones inside
+        let morton = Morton::new(5, 7);
+        let codes = morton
+            .coords()
+            .map(|(row, col)| interleave(row, col))
+            .collect::<Vec<_>>();
+        assert!(codes.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_morton_is_traversal() {
+        check_traversal(9, Morton::new);
+    }
+}
@@ -0,0 +1,191 @@
+use std::num::NonZeroUsize;
+use std::ops::Range;
+use std::thread;
+
+use super::rings::{ring_count, ring_len};
+use crate::view::MatrixView;
+
+/// The same as [`spiral`](super::spiral::spiral), but split over as many threads as the machine
+/// has cores. See [`par_spiral_with_threads`].
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::parallel::par_spiral;
+/// use week1::traversal::spiral::{create_matrix, spiral};
+///
+/// let matrix = create_matrix(100);
+/// assert_eq!(par_spiral(&matrix), spiral(&matrix));
+/// ```
+pub fn par_spiral<M>(matrix: &M) -> Vec<M::Item>
+where
+    M: MatrixView + Sync + ?Sized,
+    M::Item: Copy + Send,
+{
+    let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    par_spiral_with_threads(matrix, threads)
+}
+
+/// Constructs the spiral of a matrix like [`spiral`](super::spiral::spiral) does, with the work
+/// split over up to `threads` threads.
+///
+/// # Arguments
+///
+/// * `matrix` - A 2D matrix of T, anything that's a [`MatrixView`] and can be shared between
+///   threads
+/// * `threads` - The most threads to use. Small matrices may use fewer, since every thread gets
+///   whole rings
+///
+/// # Returns
+///
+/// A Vec<T> of the elements in spiral order, exactly the same as [`spiral`](super::spiral::spiral)
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::parallel::par_spiral_with_threads;
+///
+/// let matrix = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+/// assert_eq!(par_spiral_with_threads(&matrix, 4), vec![1, 2, 3, 6, 9, 8, 7, 4, 5]);
+/// ```
+///
+/// # Approach
+///
+/// The spiral is the rings of the matrix one after the other, and we know how long every ring is
+/// up front, so we know exactly where each ring starts in the output. The rings are split into
+/// consecutive groups with about the same number of elements (the outer rings are the biggest, so
+/// the outer groups get fewer rings), the output is split into one slice per group with
+/// `split_at_mut`, and each thread fills its own slice. Nothing is shared but the matrix, which is
+/// only read.
+///
+/// # Time and Space Complexity
+///
+/// O(n / threads) time, where n is the number of elements in the matrix, plus the cost of starting
+/// the threads, and O(n) space for the result.
+///
+/// # Panics
+///
+/// Panics if `threads` is 0.
+pub fn par_spiral_with_threads<M>(matrix: &M, threads: usize) -> Vec<M::Item>
+where
+    M: MatrixView + Sync + ?Sized,
+    M::Item: Copy + Send,
+{
+    assert!(threads > 0, "need at least one thread");
+    let (rows, cols) = matrix.dims();
+    if rows == 0 || cols == 0 {
+        return Vec::new();
+    }
+
+    // every slot gets overwritten, this just gives us initialized memory to split up without
+    // needing Default
+    let mut result = vec![matrix.get(0, 0); rows * cols];
+
+    thread::scope(|scope| {
+        let mut rest = result.as_mut_slice();
+        for rings in ring_groups(rows, cols, threads) {
+            let len = rings.clone().map(|ring| ring_len(rows, cols, ring)).sum();
+            let (out, tail) = rest.split_at_mut(len);
+            rest = tail;
+            scope.spawn(move || fill_rings(matrix, rings, out));
+        }
+    });
+
+    result
+}
+
+/// Splits the rings of a rows x cols matrix into up to `groups` runs of consecutive rings with
+/// about the same number of elements each.
+fn ring_groups(rows: usize, cols: usize, groups: usize) -> Vec<Range<usize>> {
+    let total = rows * cols;
+    let mut result = Vec::with_capacity(groups);
+    let (mut start, mut covered) = (0, 0);
+    for ring in 0..ring_count(rows, cols) {
+        covered += ring_len(rows, cols, ring);
+        // close the group once it has reached its share of the elements
+        if covered * groups >= total * (result.len() + 1) {
+            result.push(start..ring + 1);
+            start = ring + 1;
+        }
+    }
+    result
+}
+
+/// Writes the elements of `rings` to `out` in spiral order, a side of a ring at a time.
+fn fill_rings<M>(matrix: &M, rings: Range<usize>, out: &mut [M::Item])
+where
+    M: MatrixView + ?Sized,
+{
+    let (rows, cols) = matrix.dims();
+    let mut out = out.iter_mut();
+    let mut put = |row, col| *out.next().unwrap() = matrix.get(row, col);
+
+    for ring in rings {
+        let (top, left) = (ring, ring);
+        let (bottom, right) = (rows - 1 - ring, cols - 1 - ring);
+
+        (left..=right).for_each(|col| put(top, col));
+        (top + 1..=bottom).for_each(|row| put(row, right));
+        // single rows and columns don't come back the other way
+        if bottom > top {
+            (left..right).rev().for_each(|col| put(bottom, col));
+        }
+        if right > left {
+            (top + 1..bottom).rev().for_each(|row| put(row, left));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+    use crate::traversal::numbered;
+    use crate::traversal::spiral::spiral;
+
+    #[test]
+    fn test_par_spiral_matches_spiral() {
+        for rows in 0..=12 {
+            for cols in 0..=12 {
+                let matrix = numbered(rows, cols);
+                let expected = spiral(&matrix);
+                for threads in 1..=5 {
+                    assert_eq!(
+                        par_spiral_with_threads(&matrix, threads),
+                        expected,
+                        "{rows}x{cols}, {threads} threads"
+                    );
+                }
+                if rows > 0 && cols > 0 {
+                    let grid = Grid::try_from(matrix).unwrap();
+                    assert_eq!(par_spiral_with_threads(&grid, 3), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_ring_groups() {
+        // every ring is in exactly one group, in order
+        for (rows, cols) in [(1, 1), (3, 8), (100, 100), (7, 1000)] {
+            for groups in 1..=9 {
+                let split = ring_groups(rows, cols, groups);
+                assert!(!split.is_empty() && split.len() <= groups);
+                assert_eq!(split[0].start, 0);
+                assert_eq!(split.last().unwrap().end, ring_count(rows, cols));
+                assert!(split.windows(2).all(|w| w[0].end == w[1].start));
+            }
+        }
+
+        // the outer rings are bigger, so the first group gets fewer of them
+        let split = ring_groups(100, 100, 4);
+        assert_eq!(split.len(), 4);
+        assert!(split[0].len() < split[3].len());
+    }
+
+    #[test]
+    fn test_par_spiral_mega_matrix() {
+        let mega_matrix = crate::traversal::spiral::create_matrix(1000);
+        assert_eq!(par_spiral(&mega_matrix), spiral(&mega_matrix));
+    }
+}
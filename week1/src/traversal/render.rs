@@ -0,0 +1,321 @@
+use std::fmt::{Display, Write};
+
+use super::Traversal;
+use crate::view::MatrixView;
+
+/// Draws the path a [`Traversal`] takes through a matrix, either as ASCII art or as a standalone
+/// SVG image. Each cell is labelled with the step it's visited at, and optionally with the value
+/// it holds.
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::render::Render;
+/// use week1::traversal::spiral::Spiral;
+///
+/// let render = Render::new(Spiral::new(3, 3));
+/// assert_eq!(
+///     render.ascii(),
+///     "\
+/// 0 > 1 > 2
+///         v
+/// 7 > 8   3
+/// ^       v
+/// 6 < 5 < 4
+/// "
+/// );
+///
+/// let svg = render.labels(&vec![vec!['a', 'b', 'c']; 3]).svg();
+/// assert!(svg.starts_with("<svg"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Render<O> {
+    traversal: O,
+    labels: Option<Vec<String>>,
+}
+
+/// The side of a cell in the SVG, in pixels.
+const CELL: usize = 48;
+/// The space around the grid in the SVG, in pixels.
+const MARGIN: usize = 16;
+
+impl<O: Traversal> Render<O> {
+    /// A drawing of `traversal`, with just the step numbers.
+    pub fn new(traversal: O) -> Render<O> {
+        Render {
+            traversal,
+            labels: None,
+        }
+    }
+
+    /// Labels every cell with its element of `matrix` too.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `matrix` isn't the shape of the traversal.
+    pub fn labels<M>(mut self, matrix: &M) -> Render<O>
+    where
+        M: MatrixView + ?Sized,
+        M::Item: Display,
+    {
+        let (rows, cols) = self.traversal.dims();
+        assert_eq!(
+            matrix.dims(),
+            (rows, cols),
+            "the matrix is not the shape of the traversal"
+        );
+        let labels = (0..rows)
+            .flat_map(|row| (0..cols).map(move |col| (row, col)))
+            .map(|(row, col)| matrix.get(row, col).to_string())
+            .collect();
+        self.labels = Some(labels);
+        self
+    }
+
+    /// The label of a cell, if there are labels.
+    fn label(&self, row: usize, col: usize) -> Option<&str> {
+        let (_, cols) = self.traversal.dims();
+        self.labels
+            .as_ref()
+            .map(|labels| labels[row * cols + col].as_str())
+    }
+
+    /// Draws the traversal as text: one line per row, each cell showing its step number (and
+    /// `:value` if there are labels), with `<`, `>`, `^` and `v` arrows between cells that follow
+    /// each other. Steps that jump to a cell that isn't right next to the previous one, like in
+    /// [`Zigzag`](super::Zigzag) or [`Morton`](super::Morton) order, have no arrow.
+    ///
+    /// # Time and Space Complexity
+    ///
+    /// O(n) time and space, where n is the number of cells.
+    pub fn ascii(&self) -> String {
+        let (rows, cols) = self.traversal.dims();
+        let mut result = String::new();
+        if self.traversal.is_empty() {
+            return result;
+        }
+
+        let texts = (0..rows)
+            .map(|row| {
+                (0..cols)
+                    .map(|col| {
+                        let step = self.traversal.position(row, col);
+                        match self.label(row, col) {
+                            Some(label) => format!("{}:{}", step, label),
+                            None => step.to_string(),
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let width = texts.iter().flatten().map(|text| text.len()).max().unwrap();
+
+        // the arrow from each cell to the one to its right, and to the one below it
+        let mut across = vec![vec![' '; cols]; rows];
+        let mut down = vec![vec![' '; cols]; rows];
+        for ((r1, c1), (r2, c2)) in self.steps() {
+            if r1 == r2 && c1.abs_diff(c2) == 1 {
+                across[r1][c1.min(c2)] = if c2 > c1 { '>' } else { '<' };
+            } else if c1 == c2 && r1.abs_diff(r2) == 1 {
+                down[r1.min(r2)][c1] = if r2 > r1 { 'v' } else { '^' };
+            }
+        }
+
+        for row in 0..rows {
+            let mut line = String::new();
+            for (text, arrow) in texts[row].iter().zip(&across[row]) {
+                write!(line, "{:>width$} {} ", text, arrow).unwrap();
+            }
+            result.push_str(line.trim_end());
+            result.push('\n');
+
+            if row + 1 < rows {
+                // the arrows go under the last character of each cell, where the numbers line up
+                let mut line = String::new();
+                for arrow in &down[row] {
+                    write!(line, "{:>width$}   ", arrow).unwrap();
+                }
+                result.push_str(line.trim_end());
+                result.push('\n');
+            }
+        }
+        result
+    }
+
+    /// Draws the traversal as a standalone SVG image: a grid of cells, each with its step number,
+    /// its value if there are labels, and an arrow from every cell to the next one. The first cell
+    /// is marked with a dot. Unlike [`Render::ascii`], jumps get arrows too.
+    ///
+    /// # Time and Space Complexity
+    ///
+    /// O(n) time and space, where n is the number of cells.
+    pub fn svg(&self) -> String {
+        let (rows, cols) = self.traversal.dims();
+        let (width, height) = (cols * CELL + 2 * MARGIN, rows * CELL + 2 * MARGIN);
+        let center = |row: usize, col: usize| {
+            (
+                (MARGIN + col * CELL + CELL / 2) as f64,
+                (MARGIN + row * CELL + CELL / 2) as f64,
+            )
+        };
+
+        let mut svg = String::new();
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="monospace" text-anchor="middle">"#
+        )
+        .unwrap();
+        svg.push_str(
+            r##"<defs><marker id="arrow" viewBox="0 0 10 10" refX="9" refY="5" markerWidth="6" markerHeight="6" orient="auto"><path d="M0,0 L10,5 L0,10 z" fill="#3366cc"/></marker></defs>
+"##,
+        );
+        writeln!(
+            svg,
+            r#"<rect width="{width}" height="{height}" fill="white"/>"#
+        )
+        .unwrap();
+
+        for row in 0..rows {
+            for col in 0..cols {
+                let (x, y) = (MARGIN + col * CELL, MARGIN + row * CELL);
+                writeln!(
+                    svg,
+                    r##"<rect x="{x}" y="{y}" width="{CELL}" height="{CELL}" fill="none" stroke="#cccccc"/>"##
+                )
+                .unwrap();
+
+                let step = self.traversal.position(row, col);
+                let (cx, cy) = center(row, col);
+                match self.label(row, col) {
+                    Some(label) => {
+                        // the step goes in the corner so the value can have the middle
+                        writeln!(
+                            svg,
+                            r##"<text x="{}" y="{}" font-size="9" text-anchor="start" fill="#888888">{}</text>"##,
+                            x + 3,
+                            y + 10,
+                            step
+                        )
+                        .unwrap();
+                        writeln!(
+                            svg,
+                            r#"<text x="{cx}" y="{}" font-size="14">{}</text>"#,
+                            cy + 5.0,
+                            escape(label)
+                        )
+                        .unwrap();
+                    }
+                    None => {
+                        writeln!(
+                            svg,
+                            r#"<text x="{cx}" y="{}" font-size="12">{step}</text>"#,
+                            cy + 4.0
+                        )
+                        .unwrap();
+                    }
+                }
+            }
+        }
+
+        if !self.traversal.is_empty() {
+            let (x, y) = center(self.traversal.coord(0).0, self.traversal.coord(0).1);
+            writeln!(svg, r##"<circle cx="{x}" cy="{y}" r="4" fill="#3366cc"/>"##).unwrap();
+        }
+        for ((r1, c1), (r2, c2)) in self.steps() {
+            let ((x1, y1), (x2, y2)) = (center(r1, c1), center(r2, c2));
+            // stop short of the centers so the arrows don't cover the text
+            let (dx, dy) = (x2 - x1, y2 - y1);
+            let inset = 14.0 / (dx * dx + dy * dy).sqrt();
+            writeln!(
+                svg,
+                r##"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="#3366cc" stroke-width="2" marker-end="url(#arrow)"/>"##,
+                x1 + dx * inset,
+                y1 + dy * inset,
+                x2 - dx * inset,
+                y2 - dy * inset
+            )
+            .unwrap();
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Every pair of consecutive cells on the path.
+    fn steps(&self) -> impl Iterator<Item = ((usize, usize), (usize, usize))> + '_ {
+        self.traversal.coords().zip(self.traversal.coords().skip(1))
+    }
+}
+
+/// Escapes the characters that mean something in XML text.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traversal::{Hilbert, Snake, Spiral, Zigzag};
+
+    #[test]
+    fn test_render_ascii_spiral() {
+        assert_eq!(
+            Render::new(Spiral::new(3, 4)).ascii(),
+            " 0 >  1 >  2 >  3
+                v
+ 9 > 10 > 11    4
+ ^              v
+ 8 <  7 <  6 <  5
+"
+        );
+    }
+
+    #[test]
+    fn test_render_ascii_labels() {
+        let matrix = vec![vec!["a", "b", "c"], vec!["d", "e", "f"]];
+        assert_eq!(
+            Render::new(Snake::new(2, 3)).labels(&matrix).ascii(),
+            "0:a > 1:b > 2:c
+              v
+5:d < 4:e < 3:f
+"
+        );
+    }
+
+    #[test]
+    fn test_render_ascii_jumps() {
+        // the zigzag only steps to neighbours along the edges
+        assert_eq!(
+            Render::new(Zigzag::new(2, 2)).ascii(),
+            "0 > 1
+
+2 > 3
+"
+        );
+        assert_eq!(Render::new(Spiral::new(0, 4)).ascii(), "");
+    }
+
+    #[test]
+    fn test_render_svg() {
+        let matrix = vec![vec!["<a>", "b&c"], vec!["d", "e"]];
+        let svg = Render::new(Hilbert::new(2, 2)).labels(&matrix).svg();
+        assert!(
+            svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="128" height="128""#)
+        );
+        assert!(svg.ends_with("</svg>\n"));
+        // the background and one rect per cell, and one arrow per step
+        assert_eq!(svg.matches("<rect").count(), 5);
+        assert_eq!(svg.matches("<line").count(), 3);
+        assert!(svg.contains("&lt;a&gt;") && svg.contains("b&amp;c"));
+        assert!(!svg.contains("<a>"));
+    }
+
+    #[test]
+    #[should_panic(expected = "the matrix is not the shape of the traversal")]
+    fn test_render_labels_wrong_shape() {
+        Render::new(Spiral::new(3, 3)).labels(&vec![vec![1, 2], vec![3, 4]]);
+    }
+}
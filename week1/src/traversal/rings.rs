@@ -0,0 +1,475 @@
+use crate::grid::{RowMajor, RowMajorMut};
+
+/// The number of concentric rings (layers) a rows x cols matrix peels into: one for every two rows
+/// or columns of the shorter side, plus a single row or column in the middle if it's odd.
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::rings::ring_count;
+///
+/// assert_eq!(ring_count(4, 4), 2);
+/// assert_eq!(ring_count(5, 3), 2);
+/// assert_eq!(ring_count(1, 7), 1);
+/// assert_eq!(ring_count(0, 7), 0);
+/// ```
+pub fn ring_count(rows: usize, cols: usize) -> usize {
+    rows.min(cols).div_ceil(2)
+}
+
+/// The number of cells in ring `ring` of a rows x cols matrix, where ring 0 is the outermost one.
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::rings::ring_len;
+///
+/// // the outer ring of a 4x5 matrix has 14 cells, the inner 2x3 one has 6
+/// assert_eq!(ring_len(4, 5, 0), 14);
+/// assert_eq!(ring_len(4, 5, 1), 6);
+/// // a ring that is a single row or column doesn't double back
+/// assert_eq!(ring_len(3, 5, 1), 3);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the matrix doesn't have that many rings.
+pub fn ring_len(rows: usize, cols: usize, ring: usize) -> usize {
+    let (height, width) = ring_dims(rows, cols, ring);
+    if height == 1 || width == 1 {
+        height * width
+    } else {
+        2 * (height + width) - 4
+    }
+}
+
+/// The (row, col) of the cell `pos` steps clockwise around ring `ring` of a rows x cols matrix,
+/// starting from the ring's top left corner. This is the same order [`spiral`](super::spiral::spiral)
+/// walks the ring in.
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::rings::ring_coord;
+///
+/// // 0 1 2
+/// // 7 . 3
+/// // 6 5 4
+/// assert_eq!(ring_coord(3, 3, 0, 3), (1, 2));
+/// assert_eq!(ring_coord(3, 3, 0, 7), (1, 0));
+/// assert_eq!(ring_coord(3, 3, 1, 0), (1, 1));
+/// ```
+///
+/// # Panics
+///
+/// Panics if the matrix doesn't have that many rings, or `pos` is past the end of the ring.
+pub fn ring_coord(rows: usize, cols: usize, ring: usize, pos: usize) -> (usize, usize) {
+    let len = ring_len(rows, cols, ring);
+    assert!(pos < len, "position {} is outside of ring {}", pos, ring);
+
+    let (height, width) = ring_dims(rows, cols, ring);
+    let (top, left) = (ring, ring);
+    let (bottom, right) = (top + height - 1, left + width - 1);
+
+    // right along the top, down the right side, left along the bottom and up the left side
+    let mut pos = pos;
+    if pos < width {
+        return (top, left + pos);
+    }
+    pos -= width;
+    if pos < height - 1 {
+        return (top + 1 + pos, right);
+    }
+    pos -= height - 1;
+    if pos < width - 1 {
+        return (bottom, right - 1 - pos);
+    }
+    pos -= width - 1;
+    (bottom - 1 - pos, left)
+}
+
+/// The (height, width) of ring `ring`, i.e. of the part of the matrix it goes around.
+fn ring_dims(rows: usize, cols: usize, ring: usize) -> (usize, usize) {
+    assert!(
+        ring < ring_count(rows, cols),
+        "a {}x{} matrix doesn't have a ring {}",
+        rows,
+        cols,
+        ring
+    );
+    (rows - 2 * ring, cols - 2 * ring)
+}
+
+/// Peels a matrix into its concentric rings, from the outermost one in. Each ring is its own
+/// iterator over the ring's elements, clockwise from its top left corner, so chaining all of them
+/// together gives the [`spiral`](super::spiral::spiral) of the matrix.
+///
+/// # Arguments
+///
+/// * `matrix` - A 2D matrix of T, either a [`Grid`](crate::grid::Grid) or nested Vecs
+///
+/// # Returns
+///
+/// A [`Rings`] iterator that yields one [`Ring`] per layer
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::rings::rings;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3, 4],
+///     vec![5, 6, 7, 8],
+///     vec![9, 10, 11, 12],
+/// ];
+///
+/// let layers = rings(&matrix)
+///     .map(|ring| ring.copied().collect::<Vec<_>>())
+///     .collect::<Vec<_>>();
+/// assert_eq!(layers, vec![vec![1, 2, 3, 4, 8, 12, 11, 10, 9, 5], vec![6, 7]]);
+/// ```
+///
+/// # Time and Space Complexity
+///
+/// Nothing is copied, each element is looked up with [`ring_coord`] when the ring gets to it, so
+/// walking every ring is O(n) time, where n is the number of elements in the matrix, and O(1)
+/// space.
+///
+/// # Panics
+///
+/// Panics if the rows of nested Vecs don't all have the same length.
+pub fn rings<M: RowMajor + ?Sized>(matrix: &M) -> Rings<'_, M> {
+    matrix.check_rows().unwrap_or_else(|err| panic!("{}", err));
+    let (rows, cols) = matrix.dims();
+    Rings {
+        matrix,
+        front: 0,
+        back: ring_count(rows, cols),
+    }
+}
+
+/// The concentric rings of a matrix, outermost first. See [`rings`].
+pub struct Rings<'a, M: ?Sized> {
+    matrix: &'a M,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, M: RowMajor + ?Sized> Iterator for Rings<'a, M> {
+    type Item = Ring<'a, M>;
+
+    fn next(&mut self) -> Option<Ring<'a, M>> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(Ring::new(self.matrix, self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.back - self.front;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, M: RowMajor + ?Sized> DoubleEndedIterator for Rings<'a, M> {
+    fn next_back(&mut self) -> Option<Ring<'a, M>> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(Ring::new(self.matrix, self.back))
+    }
+}
+
+impl<'a, M: RowMajor + ?Sized> ExactSizeIterator for Rings<'a, M> {}
+
+/// The elements of one ring of a matrix, clockwise from its top left corner. See [`rings`].
+pub struct Ring<'a, M: ?Sized> {
+    matrix: &'a M,
+    depth: usize,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, M: RowMajor + ?Sized> Ring<'a, M> {
+    fn new(matrix: &'a M, depth: usize) -> Ring<'a, M> {
+        let (rows, cols) = matrix.dims();
+        Ring {
+            matrix,
+            depth,
+            front: 0,
+            back: ring_len(rows, cols, depth),
+        }
+    }
+
+    /// Which ring this is, 0 for the outermost one.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// The element `pos` steps clockwise around the ring from its top left corner.
+    fn element(&self, pos: usize) -> &'a M::Item {
+        let (rows, cols) = self.matrix.dims();
+        let (row, col) = ring_coord(rows, cols, self.depth, pos);
+        &self.matrix.row(row)[col]
+    }
+}
+
+impl<'a, M: RowMajor + ?Sized> Iterator for Ring<'a, M> {
+    type Item = &'a M::Item;
+
+    fn next(&mut self) -> Option<&'a M::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.element(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.back - self.front;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, M: RowMajor + ?Sized> DoubleEndedIterator for Ring<'a, M> {
+    fn next_back(&mut self) -> Option<&'a M::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.element(self.back))
+    }
+}
+
+impl<'a, M: RowMajor + ?Sized> ExactSizeIterator for Ring<'a, M> {}
+
+/// Rotates every ring of a matrix in place by `k` positions: each element moves `k` steps
+/// clockwise around its own ring, or counterclockwise if `k` is negative. Rings shorter than `k`
+/// just go around more than once.
+///
+/// # Arguments
+///
+/// * `matrix` - A 2D matrix of T, either a [`Grid`](crate::grid::Grid) or nested Vecs
+/// * `k` - How many steps to move every element, clockwise if it's positive
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::rings::rotate_rings;
+///
+/// let mut matrix = vec![
+///     vec![1, 2, 3, 4],
+///     vec![5, 6, 7, 8],
+///     vec![9, 10, 11, 12],
+///     vec![13, 14, 15, 16],
+/// ];
+///
+/// rotate_rings(&mut matrix, 1);
+/// assert_eq!(
+///     matrix,
+///     vec![
+///         vec![5, 1, 2, 3],
+///         vec![9, 10, 6, 4],
+///         vec![13, 11, 7, 8],
+///         vec![14, 15, 16, 12],
+///     ]
+/// );
+///
+/// rotate_rings(&mut matrix, -1);
+/// assert_eq!(matrix[0], vec![1, 2, 3, 4]);
+/// ```
+///
+/// # Approach
+///
+/// Same as rotating a slice with three reversals: reverse the whole ring, then reverse the first
+/// k and the last len - k elements. Reversing only ever swaps two cells, so this works for any
+/// element type, and never copies anything out of the matrix.
+///
+/// # Time and Space Complexity
+///
+/// Every element is swapped at most twice, so it's O(n) time, where n is the number of elements
+/// in the matrix, and O(1) space.
+///
+/// # Panics
+///
+/// Panics if the rows of nested Vecs don't all have the same length.
+pub fn rotate_rings<M: RowMajorMut + ?Sized>(matrix: &mut M, k: isize) {
+    matrix.check_rows().unwrap_or_else(|err| panic!("{}", err));
+    let (rows, cols) = matrix.dims();
+    for ring in 0..ring_count(rows, cols) {
+        shift_ring(matrix, ring, k);
+    }
+}
+
+/// Rotates a single ring of a matrix in place by `k` positions, like [`rotate_rings`] does to all
+/// of them.
+///
+/// # Panics
+///
+/// Panics if the matrix doesn't have that many rings, or if the rows of nested Vecs don't all have
+/// the same length.
+pub fn rotate_ring<M: RowMajorMut + ?Sized>(matrix: &mut M, ring: usize, k: isize) {
+    matrix.check_rows().unwrap_or_else(|err| panic!("{}", err));
+    shift_ring(matrix, ring, k);
+}
+
+/// [`rotate_ring`] for a matrix whose rows have already been checked.
+fn shift_ring<M: RowMajorMut + ?Sized>(matrix: &mut M, ring: usize, k: isize) {
+    let (rows, cols) = matrix.dims();
+    let len = ring_len(rows, cols, ring);
+    // moving everything k steps clockwise is the same as a right rotation of the ring's sequence
+    let shift = k.rem_euclid(len as isize) as usize;
+    if shift == 0 {
+        return;
+    }
+    reverse_ring(matrix, ring, 0, len);
+    reverse_ring(matrix, ring, 0, shift);
+    reverse_ring(matrix, ring, shift, len);
+}
+
+/// Reverses the elements at positions start..end of a ring.
+fn reverse_ring<M: RowMajorMut + ?Sized>(matrix: &mut M, ring: usize, start: usize, end: usize) {
+    let (rows, cols) = matrix.dims();
+    let (mut low, mut high) = (start, end);
+    while high - low > 1 {
+        high -= 1;
+        matrix.swap(
+            ring_coord(rows, cols, ring, low),
+            ring_coord(rows, cols, ring, high),
+        );
+        low += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+    use crate::traversal::numbered;
+    use crate::traversal::spiral::spiral;
+
+    #[test]
+    fn test_ring_count_and_len() {
+        assert_eq!(ring_count(6, 9), 3);
+        assert_eq!(ring_count(7, 9), 4);
+        assert_eq!(
+            (0..3).map(|ring| ring_len(6, 9, ring)).collect::<Vec<_>>(),
+            vec![26, 18, 10]
+        );
+        assert_eq!(ring_len(7, 9, 3), 3);
+        assert_eq!(ring_len(1, 1, 0), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "row 1 has 4 columns, but the rows before it have 2")]
+    fn test_rings_ragged() {
+        rings(&vec![vec![1, 2], vec![3, 4, 5, 6]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "row 2 has 1 columns, but the rows before it have 3")]
+    fn test_rotate_rings_ragged() {
+        rotate_rings(&mut vec![vec![1, 2, 3], vec![4, 5, 6], vec![7]], 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "a 4x4 matrix doesn't have a ring 2")]
+    fn test_ring_len_past_the_middle() {
+        ring_len(4, 4, 2);
+    }
+
+    #[test]
+    fn test_rings_make_the_spiral() {
+        for rows in 0..=8 {
+            for cols in 0..=8 {
+                let matrix = numbered(rows, cols);
+                let joined = rings(&matrix).flatten().copied().collect::<Vec<_>>();
+                assert_eq!(joined, spiral(&matrix), "{rows}x{cols}");
+
+                let lens = rings(&matrix).map(|ring| ring.len()).sum::<usize>();
+                assert_eq!(lens, rows * cols, "{rows}x{cols}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_rings_rev() {
+        let matrix = numbered(5, 5);
+        let inner = rings(&matrix)
+            .rev()
+            .map(|ring| ring.depth())
+            .collect::<Vec<_>>();
+        assert_eq!(inner, vec![2, 1, 0]);
+
+        let ring = rings(&matrix).nth(1).unwrap();
+        assert_eq!(
+            ring.rev().copied().collect::<Vec<_>>(),
+            vec![11, 16, 17, 18, 13, 8, 7, 6]
+        );
+    }
+
+    #[test]
+    fn test_rotate_rings_rectangle() {
+        // 0  1  2  3  4
+        // 5  6  7  8  9
+        // 10 11 12 13 14
+        let mut matrix = numbered(3, 5);
+        rotate_rings(&mut matrix, 2);
+        assert_eq!(
+            matrix,
+            vec![
+                vec![10, 5, 0, 1, 2],
+                vec![11, 7, 8, 6, 3],
+                vec![12, 13, 14, 9, 4],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rotate_rings_matches_rotated_sequence() {
+        for (rows, cols) in [
+            (1, 1),
+            (1, 6),
+            (6, 1),
+            (2, 2),
+            (4, 7),
+            (7, 4),
+            (5, 5),
+            (6, 6),
+        ] {
+            for k in -20..=20isize {
+                let mut matrix = numbered(rows, cols);
+                let mut expected = rings(&matrix)
+                    .map(|ring| ring.copied().collect::<Vec<_>>())
+                    .collect::<Vec<_>>();
+                for ring in &mut expected {
+                    let shift = k.rem_euclid(ring.len() as isize) as usize;
+                    ring.rotate_right(shift);
+                }
+
+                rotate_rings(&mut matrix, k);
+                let actual = rings(&matrix)
+                    .map(|ring| ring.copied().collect::<Vec<_>>())
+                    .collect::<Vec<_>>();
+                assert_eq!(actual, expected, "{rows}x{cols} by {k}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_rotate_rings_round_trip() {
+        let mut grid = Grid::from_iter(6, 9, (0..).map(|x: i32| x.to_string())).unwrap();
+        let original = grid.clone();
+
+        rotate_rings(&mut grid, 7);
+        assert_ne!(grid, original);
+        rotate_rings(&mut grid, -7);
+        assert_eq!(grid, original);
+
+        // a full turn of the outer ring leaves it where it was
+        rotate_ring(&mut grid, 0, 26);
+        assert_eq!(grid, original);
+    }
+}
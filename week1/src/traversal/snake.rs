@@ -0,0 +1,99 @@
+use super::Traversal;
+
+/// Walks a matrix row by row like [`spiral`](super::spiral::spiral) walks it ring by ring, but
+/// turning around at the end of every row (boustrophedon, like an ox ploughing a field): left to
+/// right on even rows, right to left on odd rows.
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::{Snake, Traversal};
+///
+/// // 1 2 3
+/// // 4 5 6
+/// // 7 8 9
+/// let snake = Snake::new(3, 3);
+/// let order = snake.coords().collect::<Vec<_>>();
+/// assert_eq!(
+///     order,
+///     vec![(0, 0), (0, 1), (0, 2), (1, 2), (1, 1), (1, 0), (2, 0), (2, 1), (2, 2)]
+/// );
+/// ```
+///
+/// # Time and Space Complexity
+///
+/// `coord` and `position` are O(1) time and space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Snake {
+    rows: usize,
+    cols: usize,
+}
+
+impl Snake {
+    /// The snake order of a rows x cols matrix.
+    pub fn new(rows: usize, cols: usize) -> Snake {
+        Snake { rows, cols }
+    }
+}
+
+impl Traversal for Snake {
+    fn dims(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    fn coord(&self, pos: usize) -> (usize, usize) {
+        assert!(
+            pos < self.len(),
+            "position {} is outside of the matrix",
+            pos
+        );
+        let (row, offset) = (pos / self.cols, pos % self.cols);
+        // odd rows go right to left
+        if row % 2 == 1 {
+            (row, self.cols - 1 - offset)
+        } else {
+            (row, offset)
+        }
+    }
+
+    fn position(&self, row: usize, col: usize) -> usize {
+        assert!(
+            row < self.rows && col < self.cols,
+            "({}, {}) is outside of the matrix",
+            row,
+            col
+        );
+        let offset = if row % 2 == 1 {
+            self.cols - 1 - col
+        } else {
+            col
+        };
+        row * self.cols + offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traversal::check_traversal;
+
+    #[test]
+    fn test_snake() {
+        let matrix = vec![vec![1, 2, 3, 4], vec![5, 6, 7, 8], vec![9, 10, 11, 12]];
+        assert_eq!(
+            Snake::new(3, 4).traverse(&matrix),
+            vec![1, 2, 3, 4, 8, 7, 6, 5, 9, 10, 11, 12]
+        );
+    }
+
+    #[test]
+    fn test_snake_single_column() {
+        let matrix = vec![vec![1], vec![2], vec![3]];
+        assert_eq!(Snake::new(3, 1).traverse(&matrix), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_snake_is_traversal() {
+        check_traversal(8, Snake::new);
+    }
+}
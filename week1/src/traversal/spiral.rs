@@ -0,0 +1,1931 @@
+use std::collections::VecDeque;
+use std::mem;
+
+use super::Traversal;
+use crate::grid::{RowMajor, RowMajorMut};
+use crate::view::{from_fn, MatrixView};
+
+/// Constructs a spiral represented as a Vec<T> from a 2D matrix of T. Elements of stored matrices
+/// are cloned, so for something like a matrix of Strings, use [`SpiralIter`] to get references to
+/// them instead.
+///
+/// # Arguments
+///
+/// * `matrix` - A 2D matrix of T: a [`Grid`](crate::grid::Grid), nested Vecs, or any other
+///   [`MatrixView`], like a sub-view or transposed view of one of those
+///
+/// # Returns
+///
+/// A Vec<T> representing the spiral of the matrix, i.e. the order it was traversed
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::spiral::spiral;
+///
+/// let matrix = vec![
+///     vec![1, 2, 3, 4],
+///     vec![5, 6, 7, 8],
+///     vec![9, 10, 11, 12],
+///     vec![13, 14, 15, 16],
+/// ];
+///
+/// assert_eq!(
+///     spiral(&matrix),
+///     vec![1, 2, 3, 4, 8, 12, 16, 15, 14, 13, 9, 5, 6, 7, 11, 10]
+/// );
+/// ```
+///
+/// # Approach
+///
+/// Walks the matrix a run at a time in the default [`SpiralOrder`], the same way a
+/// [`SpiralIter`] does. Runs along a row of a stored matrix are copied out as whole slices (see
+/// [`MatrixView::fold_row`]), and only the columns and computed views go an element at a time.
+/// Since we know exactly how many elements there are, the result vector is allocated once with the
+/// right capacity.
+///
+/// # Time and Space Complexity
+///
+/// The time complexity is O(n), where n is the number of elements in the matrix since each element
+/// is visited exactly once. The space complexity is also O(n) since the result vector is the same
+/// size as the matrix. If you don't need all of it, use [`SpiralIter`] directly, which is O(1).
+///
+/// # Panics
+///
+/// Panics if the rows of nested Vecs don't all have the same length.
+pub fn spiral<M>(matrix: &M) -> Vec<M::Item>
+where
+    M: MatrixView + ?Sized,
+{
+    SpiralOrder::new().spiral(matrix)
+}
+
+/// The spiral of a rows x cols matrix whose element at (row, col) is `f(row, col)`, without ever
+/// storing the matrix. Handy for huge grids whose values come from their coordinates.
+///
+/// # Arguments
+///
+/// * `rows` - The number of rows in the matrix
+/// * `cols` - The number of columns in the matrix
+/// * `f` - Computes the element at (row, col)
+///
+/// # Returns
+///
+/// A Vec<T> of the elements in spiral order
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::spiral::spiral_fn;
+///
+/// assert_eq!(
+///     spiral_fn(3, 3, |row, col| (row, col)),
+///     vec![(0, 0), (0, 1), (0, 2), (1, 2), (2, 2), (2, 1), (2, 0), (1, 0), (1, 1)]
+/// );
+/// ```
+///
+/// # Time and Space Complexity
+///
+/// `f` is called exactly once per element, so it's O(n) calls, where n is rows * cols, and O(n)
+/// space for the result only. Use [`SpiralOrder::values`] on a [`from_fn`] matrix to not even
+/// store that.
+pub fn spiral_fn<T, F>(rows: usize, cols: usize, f: F) -> Vec<T>
+where
+    F: Fn(usize, usize) -> T,
+{
+    SpiralOrder::new().spiral(&from_fn(rows, cols, f))
+}
+
+/// Which corner of the matrix a spiral is anchored to. Inward spirals start here, outward spirals
+/// end here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Corner {
+    #[default]
+    TopLeft,
+    TopRight,
+    BottomRight,
+    BottomLeft,
+}
+
+/// Which way a spiral turns at the end of each run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rotation {
+    #[default]
+    Clockwise,
+    CounterClockwise,
+}
+
+impl Rotation {
+    fn opposite(self) -> Rotation {
+        match self {
+            Rotation::Clockwise => Rotation::CounterClockwise,
+            Rotation::CounterClockwise => Rotation::Clockwise,
+        }
+    }
+}
+
+/// Describes how to walk a matrix in a spiral: which corner it's anchored to, which way it turns,
+/// and whether it winds inward from the edge to the center or outward from the center to the edge.
+/// The default is what [`spiral`] does: inward, clockwise, from the top left corner.
+///
+/// An outward spiral is the exact reverse of an inward one, so it ends at its corner. Since
+/// walking a path backwards flips the way it turns, the outward spiral with a given rotation is the
+/// reversed inward spiral with the other rotation, which keeps `rotation` meaning what it looks
+/// like on screen.
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::spiral::{Corner, Rotation, SpiralOrder};
+///
+/// let matrix = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+///
+/// let order = SpiralOrder::new()
+///     .corner(Corner::BottomLeft)
+///     .rotation(Rotation::CounterClockwise);
+/// assert_eq!(order.spiral(&matrix), vec![7, 8, 9, 6, 3, 2, 1, 4, 5]);
+///
+/// let order = SpiralOrder::new().outward();
+/// assert_eq!(order.spiral(&matrix), vec![5, 2, 3, 6, 9, 8, 7, 4, 1]);
+/// ```
+///
+/// # Approach
+///
+/// Every combination of corner and rotation is the same clockwise, top left spiral on either the
+/// matrix or its transpose, flipped vertically and/or horizontally. So we only ever walk the
+/// canonical spiral, and map its coordinates at the end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SpiralOrder {
+    corner: Corner,
+    rotation: Rotation,
+    outward: bool,
+}
+
+impl SpiralOrder {
+    /// The order [`spiral`] uses: inward, clockwise, from the top left corner.
+    pub fn new() -> SpiralOrder {
+        SpiralOrder::default()
+    }
+
+    /// Sets the corner the spiral starts from (or ends at, if it's outward).
+    pub fn corner(mut self, corner: Corner) -> SpiralOrder {
+        self.corner = corner;
+        self
+    }
+
+    /// Sets which way the spiral turns.
+    pub fn rotation(mut self, rotation: Rotation) -> SpiralOrder {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Makes the spiral wind in from the corner to the center. This is the default.
+    pub fn inward(mut self) -> SpiralOrder {
+        self.outward = false;
+        self
+    }
+
+    /// Makes the spiral unwind from the center out to the corner.
+    pub fn outward(mut self) -> SpiralOrder {
+        self.outward = true;
+        self
+    }
+
+    /// Lazily walks `matrix` in this order. Panics if the rows of nested Vecs don't all have the
+    /// same length.
+    pub fn iter<'a, M: RowMajor + ?Sized>(&self, matrix: &'a M) -> SpiralIter<'a, M> {
+        matrix.check_rows().unwrap_or_else(|err| panic!("{}", err));
+        let (rows, cols) = matrix.dims();
+        SpiralIter {
+            matrix,
+            path: SpiralPath::new(rows, cols, *self),
+        }
+    }
+
+    /// Lazily walks any [`MatrixView`] in this order, yielding its elements by value. Panics if the
+    /// rows of nested Vecs don't all have the same length.
+    pub fn values<'a, M: MatrixView + ?Sized>(&self, matrix: &'a M) -> SpiralValues<'a, M> {
+        matrix.check_shape().unwrap_or_else(|err| panic!("{}", err));
+        let (rows, cols) = matrix.dims();
+        SpiralValues {
+            matrix,
+            path: SpiralPath::new(rows, cols, *self),
+        }
+    }
+
+    /// Collects `matrix` in this order into a Vec.
+    pub fn spiral<M: MatrixView + ?Sized>(&self, matrix: &M) -> Vec<M::Item> {
+        let values = self.values(matrix);
+
+        // this shaves off a lot of time as there is no need to reallocate memory in the future
+        let mut result = Vec::with_capacity(values.len());
+        // for_each goes a run at a time, copying rows of stored matrices as slices, which is a lot
+        // faster than calling next over and over
+        values.for_each(|x| result.push(x));
+
+        result
+    }
+
+    /// Rebuilds a rows x cols matrix from a sequence in this order. See [`unspiral`].
+    pub fn unspiral<T: Clone>(
+        &self,
+        seq: &[T],
+        rows: usize,
+        cols: usize,
+    ) -> Result<Vec<Vec<T>>, UnspiralError> {
+        if seq.len() != rows * cols {
+            return Err(UnspiralError::LengthMismatch {
+                expected: rows * cols,
+                actual: seq.len(),
+            });
+        }
+        self.fill(rows, cols, seq.iter().cloned())
+    }
+
+    /// Builds a rows x cols matrix by laying out `values` in this order. See [`spiral_fill`].
+    pub fn fill<T, I>(
+        &self,
+        rows: usize,
+        cols: usize,
+        values: I,
+    ) -> Result<Vec<Vec<T>>, UnspiralError>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let expected = rows * cols;
+        let mut values = values.into_iter();
+
+        // T doesn't have to be Default or Clone, so cells start out empty until we get to them
+        let mut cells = (0..expected).map(|_| None).collect::<Vec<Option<T>>>();
+        for (placed, (row, col)) in SpiralPath::new(rows, cols, *self).enumerate() {
+            match values.next() {
+                Some(value) => cells[row * cols + col] = Some(value),
+                None => {
+                    return Err(UnspiralError::LengthMismatch {
+                        expected,
+                        actual: placed,
+                    })
+                }
+            }
+        }
+
+        // every cell is on the path, so they're all filled in by now
+        let mut cells = cells.into_iter().map(Option::unwrap);
+        Ok((0..rows)
+            .map(|_| cells.by_ref().take(cols).collect())
+            .collect())
+    }
+}
+
+/// The inverse of [`spiral`]: rebuilds a rows x cols matrix from the sequence of its elements in
+/// spiral order, such that `unspiral(&spiral(&matrix), rows, cols) == Ok(matrix)`.
+///
+/// # Arguments
+///
+/// * `seq` - The elements of the matrix in spiral order
+/// * `rows` - The number of rows in the matrix
+/// * `cols` - The number of columns in the matrix
+///
+/// # Returns
+///
+/// The matrix, or an error if `seq` doesn't have exactly rows * cols elements
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::spiral::unspiral;
+///
+/// let matrix = unspiral(&[1, 2, 3, 6, 9, 8, 7, 4, 5], 3, 3).unwrap();
+/// assert_eq!(matrix, vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]);
+///
+/// assert!(unspiral(&[1, 2, 3], 2, 2).is_err());
+/// ```
+///
+/// # Approach
+///
+/// Walk the coordinates of the spiral, and put each element of the sequence where the spiral would
+/// have read it from.
+///
+/// # Time and Space Complexity
+///
+/// The time complexity is O(n), where n is rows * cols, since every element is placed once. The
+/// space complexity is O(n) for the result.
+pub fn unspiral<T: Clone>(
+    seq: &[T],
+    rows: usize,
+    cols: usize,
+) -> Result<Vec<Vec<T>>, UnspiralError> {
+    SpiralOrder::new().unspiral(seq, rows, cols)
+}
+
+/// Builds a rows x cols matrix by laying out the first rows * cols values of an iterator in spiral
+/// order. Values after that are left in the iterator, so it can be infinite.
+///
+/// # Arguments
+///
+/// * `rows` - The number of rows in the matrix
+/// * `cols` - The number of columns in the matrix
+/// * `values` - The values to fill the matrix with, in spiral order
+///
+/// # Returns
+///
+/// The matrix, or an error if `values` runs out before the matrix is full
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::spiral::spiral_fill;
+///
+/// let matrix = spiral_fill(3, 3, 1..).unwrap();
+/// assert_eq!(matrix, vec![vec![1, 2, 3], vec![8, 9, 4], vec![7, 6, 5]]);
+/// ```
+///
+/// # Time and Space Complexity
+///
+/// The time complexity is O(n), where n is rows * cols. The space complexity is O(n) for the
+/// result.
+pub fn spiral_fill<T, I>(rows: usize, cols: usize, values: I) -> Result<Vec<Vec<T>>, UnspiralError>
+where
+    I: IntoIterator<Item = T>,
+{
+    SpiralOrder::new().fill(rows, cols, values)
+}
+
+/// Finds where the element at (row, col) of a rows x cols matrix ends up in [`spiral`], without
+/// walking the spiral.
+///
+/// # Arguments
+///
+/// * `rows` - The number of rows in the matrix
+/// * `cols` - The number of columns in the matrix
+/// * `row` - The row of the element
+/// * `col` - The column of the element
+///
+/// # Returns
+///
+/// The index of the element in the spiral
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::spiral::spiral_position;
+///
+/// // 1  2  3  4
+/// // 10 11 12 5
+/// // 9  8  7  6
+/// assert_eq!(spiral_position(3, 4, 0, 0), 0);
+/// assert_eq!(spiral_position(3, 4, 2, 1), 7);
+/// assert_eq!(spiral_position(3, 4, 1, 2), 11);
+/// ```
+///
+/// # Approach
+///
+/// The ring an element is on is its distance to the closest edge. Every ring outside of it is a
+/// full rectangle, and ring i of a rows x cols matrix has 2 * (rows + cols) - 8i - 4 elements, so
+/// the rings before ring k add up to 2k * (rows + cols - 2k). From there it's just a matter of
+/// which side of the ring the element is on.
+///
+/// # Time and Space Complexity
+///
+/// O(1) time and space.
+///
+/// # Panics
+///
+/// Panics if (row, col) is outside of the matrix.
+pub fn spiral_position(rows: usize, cols: usize, row: usize, col: usize) -> usize {
+    assert!(
+        row < rows && col < cols,
+        "({}, {}) is outside of a {}x{} matrix",
+        row,
+        col,
+        rows,
+        cols
+    );
+
+    let ring = row.min(col).min(rows - 1 - row).min(cols - 1 - col);
+    let (top, bottom) = (ring, rows - 1 - ring);
+    let (left, right) = (ring, cols - 1 - ring);
+    let (height, width) = (bottom - top + 1, right - left + 1);
+    let before = cells_before_ring(rows, cols, ring);
+
+    if row == top {
+        before + (col - left)
+    } else if col == right {
+        before + width + (row - top - 1)
+    } else if row == bottom {
+        before + width + (height - 1) + (right - 1 - col)
+    } else {
+        before + width + (height - 1) + (width - 1) + (bottom - 1 - row)
+    }
+}
+
+/// The inverse of [`spiral_position`]: finds the (row, col) of the element at index `pos` of the
+/// [`spiral`] of a rows x cols matrix, without walking the spiral.
+///
+/// # Arguments
+///
+/// * `rows` - The number of rows in the matrix
+/// * `cols` - The number of columns in the matrix
+/// * `pos` - The index in the spiral
+///
+/// # Returns
+///
+/// The (row, col) of the element in the matrix
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::spiral::spiral_coord;
+///
+/// // 1  2  3  4
+/// // 10 11 12 5
+/// // 9  8  7  6
+/// assert_eq!(spiral_coord(3, 4, 0), (0, 0));
+/// assert_eq!(spiral_coord(3, 4, 7), (2, 1));
+/// assert_eq!(spiral_coord(3, 4, 11), (1, 2));
+/// ```
+///
+/// # Approach
+///
+/// The number of elements before ring k is 2k * (rows + cols - 2k) (see [`spiral_position`]), so
+/// the ring `pos` is on is the largest k where that is at most `pos`. That's a quadratic in k,
+/// which we solve with an integer square root, then nudge the answer to make up for the rounding.
+/// The offset into the ring then tells us which side we're on.
+///
+/// # Time and Space Complexity
+///
+/// O(1) time and space, give or take the integer square root.
+///
+/// # Panics
+///
+/// Panics if `pos` is not less than rows * cols.
+pub fn spiral_coord(rows: usize, cols: usize, pos: usize) -> (usize, usize) {
+    assert!(
+        pos < rows * cols,
+        "position {} is outside of a {}x{} matrix",
+        pos,
+        rows,
+        cols
+    );
+
+    // smaller root of 4k^2 - 2(rows + cols)k + pos = 0
+    let perimeter = rows + cols;
+    let mut ring = (perimeter - (perimeter * perimeter - 4 * pos).isqrt()) / 4;
+    let last_ring = rows.min(cols).div_ceil(2) - 1;
+    ring = ring.min(last_ring);
+    while cells_before_ring(rows, cols, ring) > pos {
+        ring -= 1;
+    }
+    while ring < last_ring && cells_before_ring(rows, cols, ring + 1) <= pos {
+        ring += 1;
+    }
+
+    let (top, bottom) = (ring, rows - 1 - ring);
+    let (left, right) = (ring, cols - 1 - ring);
+    let (height, width) = (bottom - top + 1, right - left + 1);
+    let mut offset = pos - cells_before_ring(rows, cols, ring);
+
+    if offset < width {
+        return (top, left + offset);
+    }
+    offset -= width;
+    if offset < height - 1 {
+        return (top + 1 + offset, right);
+    }
+    offset -= height - 1;
+    if offset < width - 1 {
+        return (bottom, right - 1 - offset);
+    }
+    offset -= width - 1;
+    (bottom - 1 - offset, left)
+}
+
+/// The number of elements in the rings outside of `ring`, i.e. the sum of 2 * (rows + cols) - 8i - 4
+/// for i in 0..ring. Only valid for rings that actually exist, since all the rings outside of one
+/// are full rectangles.
+fn cells_before_ring(rows: usize, cols: usize, ring: usize) -> usize {
+    2 * ring * (rows + cols - 2 * ring)
+}
+
+/// The spiral of a rows x cols matrix in some [`SpiralOrder`] as a [`Traversal`], so it can be
+/// used anywhere the other orders can. `coord` and `position` are [`spiral_coord`] and
+/// [`spiral_position`] of the canonical spiral, mapped onto the real matrix.
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::spiral::{Rotation, Spiral, SpiralOrder};
+/// use week1::traversal::Traversal;
+///
+/// let order = SpiralOrder::new().rotation(Rotation::CounterClockwise);
+/// let traversal = Spiral::with_order(3, 3, order);
+/// assert_eq!(traversal.coord(1), (1, 0));
+/// assert_eq!(traversal.position(0, 1), 7);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Spiral {
+    path: SpiralPath,
+}
+
+impl Spiral {
+    /// The spiral of a rows x cols matrix in the same order as [`spiral`].
+    pub fn new(rows: usize, cols: usize) -> Spiral {
+        Spiral::with_order(rows, cols, SpiralOrder::new())
+    }
+
+    /// The spiral of a rows x cols matrix in any order.
+    pub fn with_order(rows: usize, cols: usize, order: SpiralOrder) -> Spiral {
+        Spiral {
+            path: SpiralPath::new(rows, cols, order),
+        }
+    }
+}
+
+impl Traversal for Spiral {
+    fn dims(&self) -> (usize, usize) {
+        (self.path.rows, self.path.cols)
+    }
+
+    fn coord(&self, pos: usize) -> (usize, usize) {
+        let path = &self.path;
+        assert!(
+            pos < self.len(),
+            "position {} is outside of the matrix",
+            pos
+        );
+        // outward spirals are the canonical one backwards
+        let pos = if path.outward {
+            self.len() - 1 - pos
+        } else {
+            pos
+        };
+        path.map_coord(spiral_coord(path.height, path.width, pos))
+    }
+
+    fn position(&self, row: usize, col: usize) -> usize {
+        let path = &self.path;
+        assert!(
+            row < path.rows && col < path.cols,
+            "({}, {}) is outside of the matrix",
+            row,
+            col
+        );
+        let (i, j) = path.unmap_coord((row, col));
+        let pos = spiral_position(path.height, path.width, i, j);
+        if path.outward {
+            self.len() - 1 - pos
+        } else {
+            pos
+        }
+    }
+}
+
+/// The reasons a matrix can't be rebuilt from a spiral ordered sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnspiralError {
+    /// The sequence had `actual` elements, but the matrix has `expected` cells.
+    LengthMismatch { expected: usize, actual: usize },
+}
+
+impl std::fmt::Display for UnspiralError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            UnspiralError::LengthMismatch { expected, actual } => write!(
+                f,
+                "expected {} elements to fill the matrix, got {}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+impl std::error::Error for UnspiralError {}
+
+/// A lazy iterator over the elements of a 2D matrix in spiral order, yielding references to the
+/// elements instead of copying them into a new Vec. It can also be walked from the back, which
+/// starts at the center of the spiral.
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::spiral::SpiralIter;
+///
+/// let matrix = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+///
+/// let mut iter = SpiralIter::new(&matrix);
+/// assert_eq!(iter.len(), 9);
+/// assert_eq!(iter.next(), Some(&1));
+/// assert_eq!(iter.next_back(), Some(&5));
+/// assert_eq!(iter.len(), 7);
+/// ```
+///
+/// # Approach
+///
+/// The spiral is made of concentric rings, and each ring is made of (up to) 4 runs: along the top
+/// row, down the right column, back along the bottom row and up the left column. A position in the
+/// spiral is then just a ring, a direction and an offset within that run, which maps to a
+/// (row, column) pair with a bit of arithmetic. We keep one such cursor for the front of the
+/// iterator and one for the back, plus a count of how many elements are left between them.
+///
+/// Consuming the whole iterator (`sum`, `for_each`, etc.) goes a run at a time instead of an
+/// element at a time, so rows are walked as plain slices.
+///
+/// # Time and Space Complexity
+///
+/// Each call to `next` or `next_back` is O(1), so walking the whole matrix is O(n). The space
+/// complexity is O(1) since nothing is allocated.
+pub struct SpiralIter<'a, M: ?Sized> {
+    matrix: &'a M,
+    path: SpiralPath,
+}
+
+impl<'a, M: RowMajor + ?Sized> SpiralIter<'a, M> {
+    /// Creates an iterator over `matrix` in the same order as [`spiral`]. Use [`SpiralOrder`] for
+    /// the other orders.
+    pub fn new(matrix: &'a M) -> SpiralIter<'a, M> {
+        SpiralOrder::new().iter(matrix)
+    }
+}
+
+impl<'a, M: RowMajor + ?Sized> Iterator for SpiralIter<'a, M> {
+    type Item = &'a M::Item;
+
+    fn next(&mut self) -> Option<&'a M::Item> {
+        self.path
+            .next()
+            .map(|(row, col)| &self.matrix.row(row)[col])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.path.size_hint()
+    }
+
+    fn fold<B, F>(mut self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, &'a M::Item) -> B,
+    {
+        let mut acc = init;
+        while let Some(run) = self.path.next_run() {
+            acc = run.fold(self.matrix, acc, &mut f);
+        }
+        acc
+    }
+}
+
+impl<'a, M: RowMajor + ?Sized> DoubleEndedIterator for SpiralIter<'a, M> {
+    fn next_back(&mut self) -> Option<&'a M::Item> {
+        self.path
+            .next_back()
+            .map(|(row, col)| &self.matrix.row(row)[col])
+    }
+
+    fn rfold<B, F>(mut self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, &'a M::Item) -> B,
+    {
+        let mut acc = init;
+        while let Some(run) = self.path.next_back_run() {
+            acc = run.fold(self.matrix, acc, &mut f);
+        }
+        acc
+    }
+}
+
+impl<'a, M: RowMajor + ?Sized> ExactSizeIterator for SpiralIter<'a, M> {}
+
+/// A lazy iterator over the elements of any [`MatrixView`] in spiral order, yielding them by value.
+/// This is what [`spiral`] and [`spiral_fn`] collect, and what to use on views that compute their
+/// elements, where there is nothing to borrow. See [`SpiralOrder::values`].
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::spiral::SpiralOrder;
+/// use week1::view::from_fn;
+///
+/// let matrix = from_fn(1000, 1000, |row, col| row * 1000 + col);
+/// let mut values = SpiralOrder::new().values(&matrix);
+/// assert_eq!(values.nth(1000), Some(1999));
+/// assert_eq!(values.next_back(), Some(500 * 1000 + 499));
+/// ```
+pub struct SpiralValues<'a, M: ?Sized> {
+    matrix: &'a M,
+    path: SpiralPath,
+}
+
+impl<'a, M: MatrixView + ?Sized> Iterator for SpiralValues<'a, M> {
+    type Item = M::Item;
+
+    fn next(&mut self) -> Option<M::Item> {
+        self.path.next().map(|(row, col)| self.matrix.get(row, col))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.path.size_hint()
+    }
+
+    fn fold<B, F>(mut self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, M::Item) -> B,
+    {
+        let mut acc = init;
+        while let Some(run) = self.path.next_run() {
+            acc = run.fold_values(self.matrix, acc, &mut f);
+        }
+        acc
+    }
+}
+
+impl<'a, M: MatrixView + ?Sized> DoubleEndedIterator for SpiralValues<'a, M> {
+    fn next_back(&mut self) -> Option<M::Item> {
+        self.path
+            .next_back()
+            .map(|(row, col)| self.matrix.get(row, col))
+    }
+
+    fn rfold<B, F>(mut self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, M::Item) -> B,
+    {
+        let mut acc = init;
+        while let Some(run) = self.path.next_back_run() {
+            acc = run.fold_values(self.matrix, acc, &mut f);
+        }
+        acc
+    }
+}
+
+impl<'a, M: MatrixView + ?Sized> ExactSizeIterator for SpiralValues<'a, M> {}
+
+/// Walks `matrix` in the same order as [`spiral`], yielding mutable references, so the matrix can
+/// be changed in place along the spiral.
+///
+/// # Arguments
+///
+/// * `matrix` - A 2D matrix of T that can be changed in place: a [`Grid`](crate::grid::Grid) or
+///   nested Vecs
+///
+/// # Returns
+///
+/// A [`SpiralIterMut`] over the elements of the matrix
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::spiral::spiral_mut;
+///
+/// let mut matrix = vec![vec![0; 3]; 3];
+/// for (cell, step) in spiral_mut(&mut matrix).zip(1..) {
+///     *cell = step;
+/// }
+/// assert_eq!(matrix, vec![vec![1, 2, 3], vec![8, 9, 4], vec![7, 6, 5]]);
+///
+/// let mut names = vec![vec![String::from("a"), String::from("b")]];
+/// spiral_mut(&mut names).for_each(|name| name.push('!'));
+/// assert_eq!(names, vec![vec!["a!", "b!"]]);
+/// ```
+pub fn spiral_mut<M: RowMajorMut + ?Sized>(matrix: &mut M) -> SpiralIterMut<'_, M::Item> {
+    SpiralIterMut::new(matrix)
+}
+
+/// A lazy iterator over mutable references to the elements of a matrix in spiral order. See
+/// [`spiral_mut`].
+///
+/// # Approach
+///
+/// Handing out `&mut T`s in an order that jumps between rows means borrowing every row at once, so
+/// we keep all of the rows as mutable slices and shrink them as we go. Every run of the spiral
+/// eats an end off of what's left: going right takes the whole top row, going down takes the last
+/// element of every row in between, going left takes the whole bottom row, and going up takes the
+/// first element of every row in between. So each element is split off the end of a slice exactly
+/// once, and nothing is ever borrowed twice.
+///
+/// # Time and Space Complexity
+///
+/// Each call to `next` is O(1), so walking the whole matrix is O(n). The rows are kept in a
+/// VecDeque, so the space complexity is O(rows).
+pub struct SpiralIterMut<'a, T> {
+    /// What's left of the rows that still have elements in them.
+    rows: VecDeque<&'a mut [T]>,
+    direction: Direction,
+    /// The index into `rows` of the next element of a vertical run.
+    row: usize,
+    remaining: usize,
+}
+
+impl<'a, T> SpiralIterMut<'a, T> {
+    /// Creates an iterator over `matrix` in the same order as [`spiral`].
+    pub fn new<M: RowMajorMut<Item = T> + ?Sized>(matrix: &'a mut M) -> SpiralIterMut<'a, T> {
+        let (rows, cols) = matrix.dims();
+        SpiralIterMut {
+            rows: matrix.rows_mut().into(),
+            direction: Direction::Right,
+            row: 0,
+            remaining: rows * cols,
+        }
+    }
+}
+
+impl<'a, T> Iterator for SpiralIterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        // every row is the same length between runs, so as long as something is left, the run
+        // we're on or the one right after it has an element for us
+        loop {
+            match self.direction {
+                Direction::Right => match self.rows.front_mut().map(mem::take) {
+                    Some([first, rest @ ..]) => {
+                        self.rows[0] = rest;
+                        return Some(first);
+                    }
+                    _ => {
+                        self.rows.pop_front();
+                        self.direction = Direction::Down;
+                        self.row = 0;
+                    }
+                },
+                Direction::Down if self.row < self.rows.len() => {
+                    let (last, rest) = mem::take(&mut self.rows[self.row]).split_last_mut()?;
+                    self.rows[self.row] = rest;
+                    self.row += 1;
+                    return Some(last);
+                }
+                Direction::Down => self.direction = Direction::Left,
+                Direction::Left => match self.rows.back_mut().map(mem::take) {
+                    Some([rest @ .., last]) => {
+                        *self.rows.back_mut().unwrap() = rest;
+                        return Some(last);
+                    }
+                    _ => {
+                        self.rows.pop_back();
+                        self.direction = Direction::Up;
+                        self.row = self.rows.len();
+                    }
+                },
+                Direction::Up if self.row > 0 => {
+                    self.row -= 1;
+                    let (first, rest) = mem::take(&mut self.rows[self.row]).split_first_mut()?;
+                    self.rows[self.row] = rest;
+                    return Some(first);
+                }
+                Direction::Up => self.direction = Direction::Right,
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for SpiralIterMut<'a, T> {}
+
+/// The (row, column) coordinates of a rows x cols matrix in some [`SpiralOrder`].
+///
+/// Internally this always walks the canonical clockwise, top left, inward spiral of a height x
+/// width matrix (which is rows x cols, or cols x rows if transposed), and maps each coordinate onto
+/// the real matrix. Outward spirals just swap which end of the canonical walk we take from.
+#[derive(Debug, Clone, Copy)]
+struct SpiralPath {
+    rows: usize,
+    cols: usize,
+    height: usize,
+    width: usize,
+    transpose: bool,
+    flip_rows: bool,
+    flip_cols: bool,
+    outward: bool,
+    front: Cursor,
+    back: Cursor,
+    remaining: usize,
+}
+
+/// A straight stretch of the spiral: `len` elements starting at (row, col) going in `direction`.
+struct Run {
+    row: usize,
+    col: usize,
+    direction: Direction,
+    len: usize,
+}
+
+impl SpiralPath {
+    fn new(rows: usize, cols: usize, order: SpiralOrder) -> SpiralPath {
+        // an outward spiral is the reversed inward spiral that turns the other way
+        let rotation = if order.outward {
+            order.rotation.opposite()
+        } else {
+            order.rotation
+        };
+        // starting from the top left or bottom right corner, the first run of a clockwise spiral
+        // is horizontal, and otherwise it's vertical, which means walking the transpose
+        let diagonal = matches!(order.corner, Corner::TopLeft | Corner::BottomRight);
+        let transpose = diagonal != (rotation == Rotation::Clockwise);
+        let flip_rows = matches!(order.corner, Corner::BottomLeft | Corner::BottomRight);
+        let flip_cols = matches!(order.corner, Corner::TopRight | Corner::BottomRight);
+
+        let (height, width) = if transpose {
+            (cols, rows)
+        } else {
+            (rows, cols)
+        };
+
+        SpiralPath {
+            rows,
+            cols,
+            height,
+            width,
+            transpose,
+            flip_rows,
+            flip_cols,
+            outward: order.outward,
+            front: Cursor::first(width),
+            back: Cursor::last(height, width),
+            remaining: rows * cols,
+        }
+    }
+
+    /// Maps a coordinate in the canonical spiral onto the real matrix.
+    fn map_coord(&self, (i, j): (usize, usize)) -> (usize, usize) {
+        let (row, col) = if self.transpose { (j, i) } else { (i, j) };
+        (
+            if self.flip_rows {
+                self.rows - 1 - row
+            } else {
+                row
+            },
+            if self.flip_cols {
+                self.cols - 1 - col
+            } else {
+                col
+            },
+        )
+    }
+
+    /// Maps a coordinate in the real matrix back onto the canonical spiral.
+    fn unmap_coord(&self, (row, col): (usize, usize)) -> (usize, usize) {
+        let row = if self.flip_rows {
+            self.rows - 1 - row
+        } else {
+            row
+        };
+        let col = if self.flip_cols {
+            self.cols - 1 - col
+        } else {
+            col
+        };
+        if self.transpose {
+            (col, row)
+        } else {
+            (row, col)
+        }
+    }
+
+    /// Maps a direction in the canonical spiral onto the real matrix.
+    fn map_direction(&self, mut direction: Direction) -> Direction {
+        if self.transpose {
+            direction = direction.transpose();
+        }
+        if self.flip_rows {
+            direction = direction.flip_rows();
+        }
+        if self.flip_cols {
+            direction = direction.flip_cols();
+        }
+        direction
+    }
+
+    /// Takes the first remaining element of the canonical spiral.
+    fn take_first(&mut self) -> (usize, usize) {
+        let coord = self.map_coord(self.front.coord(self.height, self.width));
+        self.remaining -= 1;
+        // only move on if there's something left, otherwise we'd walk off the end of the spiral
+        if self.remaining > 0 {
+            self.front.advance(self.height, self.width);
+        }
+        coord
+    }
+
+    /// Takes the last remaining element of the canonical spiral.
+    fn take_last(&mut self) -> (usize, usize) {
+        let coord = self.map_coord(self.back.coord(self.height, self.width));
+        self.remaining -= 1;
+        if self.remaining > 0 {
+            self.back.retreat(self.height, self.width);
+        }
+        coord
+    }
+
+    /// Takes the rest of the run the front cursor is on, going forwards.
+    fn take_first_run(&mut self) -> Run {
+        let len = (self.front.len - self.front.offset).min(self.remaining);
+        let (row, col) = self.map_coord(self.front.coord(self.height, self.width));
+        let direction = self.map_direction(self.front.direction);
+
+        self.remaining -= len;
+        if self.remaining > 0 {
+            self.front.offset += len - 1;
+            self.front.advance(self.height, self.width);
+        }
+        Run {
+            row,
+            col,
+            direction,
+            len,
+        }
+    }
+
+    /// Takes the rest of the run the back cursor is on, going backwards.
+    fn take_last_run(&mut self) -> Run {
+        let len = (self.back.offset + 1).min(self.remaining);
+        let (row, col) = self.map_coord(self.back.coord(self.height, self.width));
+        let direction = self.map_direction(self.back.direction).opposite();
+
+        self.remaining -= len;
+        if self.remaining > 0 {
+            self.back.offset -= len - 1;
+            self.back.retreat(self.height, self.width);
+        }
+        Run {
+            row,
+            col,
+            direction,
+            len,
+        }
+    }
+
+    /// Takes the next straight stretch of the path, so callers can walk it as a slice.
+    fn next_run(&mut self) -> Option<Run> {
+        match (self.remaining, self.outward) {
+            (0, _) => None,
+            (_, false) => Some(self.take_first_run()),
+            (_, true) => Some(self.take_last_run()),
+        }
+    }
+
+    /// Takes the last straight stretch of the path, walking it backwards.
+    fn next_back_run(&mut self) -> Option<Run> {
+        match (self.remaining, self.outward) {
+            (0, _) => None,
+            (_, false) => Some(self.take_last_run()),
+            (_, true) => Some(self.take_first_run()),
+        }
+    }
+}
+
+impl Iterator for SpiralPath {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        match (self.remaining, self.outward) {
+            (0, _) => None,
+            (_, false) => Some(self.take_first()),
+            (_, true) => Some(self.take_last()),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl DoubleEndedIterator for SpiralPath {
+    fn next_back(&mut self) -> Option<(usize, usize)> {
+        match (self.remaining, self.outward) {
+            (0, _) => None,
+            (_, false) => Some(self.take_last()),
+            (_, true) => Some(self.take_first()),
+        }
+    }
+}
+
+impl Run {
+    /// The (row, col) of every element of the run, in order.
+    fn cells(&self) -> impl Iterator<Item = (usize, usize)> {
+        let Run {
+            row,
+            col,
+            direction,
+            len,
+        } = *self;
+        (0..len).map(move |step| match direction {
+            Direction::Right => (row, col + step),
+            Direction::Left => (row, col - step),
+            Direction::Down => (row + step, col),
+            Direction::Up => (row - step, col),
+        })
+    }
+
+    /// Feeds every element of the run to `f` by value. Runs along a row go through
+    /// [`MatrixView::fold_row`], which copies whole slices out of stored matrices, and runs along
+    /// a column call `get` for each element.
+    fn fold_values<M, B, F>(&self, matrix: &M, acc: B, f: &mut F) -> B
+    where
+        M: MatrixView + ?Sized,
+        F: FnMut(B, M::Item) -> B,
+    {
+        let Run {
+            row,
+            col,
+            direction,
+            len,
+        } = *self;
+        match direction {
+            Direction::Right => matrix.fold_row(row, col..col + len, acc, f),
+            Direction::Left => matrix.rfold_row(row, col + 1 - len..col + 1, acc, f),
+            Direction::Down | Direction::Up => self
+                .cells()
+                .fold(acc, |acc, (row, col)| f(acc, matrix.get(row, col))),
+        }
+    }
+
+    /// Feeds every element of the run to `f`, walking rows as slices.
+    fn fold<'a, M, B, F>(&self, matrix: &'a M, acc: B, f: &mut F) -> B
+    where
+        M: RowMajor + ?Sized,
+        F: FnMut(B, &'a M::Item) -> B,
+    {
+        let Run {
+            row,
+            col,
+            direction,
+            len,
+        } = *self;
+        match direction {
+            Direction::Right => matrix.row(row)[col..col + len].iter().fold(acc, f),
+            Direction::Left => matrix.row(row)[col + 1 - len..=col]
+                .iter()
+                .rev()
+                .fold(acc, f),
+            Direction::Down => (row..row + len).map(|r| &matrix.row(r)[col]).fold(acc, f),
+            Direction::Up => (row + 1 - len..=row)
+                .rev()
+                .map(|r| &matrix.row(r)[col])
+                .fold(acc, f),
+        }
+    }
+}
+
+/// Which way a run of the spiral goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Right,
+    Down,
+    Left,
+    Up,
+}
+
+impl Direction {
+    /// The next direction after turning clockwise.
+    fn turn(self) -> Direction {
+        match self {
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+            Direction::Up => Direction::Right,
+        }
+    }
+
+    /// The previous direction, i.e. turning counter-clockwise.
+    fn turn_back(self) -> Direction {
+        self.turn().turn().turn()
+    }
+
+    fn opposite(self) -> Direction {
+        self.turn().turn()
+    }
+
+    /// The same direction in the transposed matrix.
+    fn transpose(self) -> Direction {
+        match self {
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Up,
+            Direction::Up => Direction::Left,
+        }
+    }
+
+    /// The same direction in the matrix flipped upside down.
+    fn flip_rows(self) -> Direction {
+        match self {
+            Direction::Down => Direction::Up,
+            Direction::Up => Direction::Down,
+            horizontal => horizontal,
+        }
+    }
+
+    /// The same direction in the matrix flipped left to right.
+    fn flip_cols(self) -> Direction {
+        match self {
+            Direction::Right => Direction::Left,
+            Direction::Left => Direction::Right,
+            vertical => vertical,
+        }
+    }
+}
+
+/// A position in the clockwise, top left spiral: which ring we're on, which way we're moving along
+/// it, and how far along that run we are. The length of the current run is cached so that stepping
+/// forward doesn't need to recompute it.
+#[derive(Debug, Clone, Copy)]
+struct Cursor {
+    ring: usize,
+    direction: Direction,
+    offset: usize,
+    len: usize,
+}
+
+impl Cursor {
+    /// The first element of the spiral, i.e. the top left corner.
+    fn first(cols: usize) -> Cursor {
+        Cursor {
+            ring: 0,
+            direction: Direction::Right,
+            offset: 0,
+            len: cols,
+        }
+    }
+
+    /// The last element of the spiral, which is at the end of the last non-empty run of the
+    /// innermost ring. Same as `first` if the matrix is empty.
+    fn last(rows: usize, cols: usize) -> Cursor {
+        if rows == 0 || cols == 0 {
+            return Cursor::first(cols);
+        }
+        let ring = rows.min(cols).div_ceil(2) - 1;
+        let direction = [
+            Direction::Up,
+            Direction::Left,
+            Direction::Down,
+            Direction::Right,
+        ]
+        .into_iter()
+        .find(|&direction| run_len(rows, cols, ring, direction) > 0)
+        .unwrap();
+        let len = run_len(rows, cols, ring, direction);
+
+        Cursor {
+            ring,
+            direction,
+            offset: len - 1,
+            len,
+        }
+    }
+
+    /// Maps the cursor to the (row, column) of the element it points at.
+    fn coord(&self, rows: usize, cols: usize) -> (usize, usize) {
+        let (top, bottom) = (self.ring, rows - 1 - self.ring);
+        let (left, right) = (self.ring, cols - 1 - self.ring);
+        match self.direction {
+            Direction::Right => (top, left + self.offset),
+            Direction::Down => (top + 1 + self.offset, right),
+            Direction::Left => (bottom, right - 1 - self.offset),
+            Direction::Up => (bottom - 1 - self.offset, left),
+        }
+    }
+
+    /// Steps to the next element, turning (and moving into the next ring) as needed. Must not be
+    /// called on the last element.
+    fn advance(&mut self, rows: usize, cols: usize) {
+        self.offset += 1;
+        while self.offset >= self.len {
+            self.offset = 0;
+            self.direction = self.direction.turn();
+            if self.direction == Direction::Right {
+                self.ring += 1;
+            }
+            self.len = run_len(rows, cols, self.ring, self.direction);
+        }
+    }
+
+    /// Steps to the previous element, turning back (and moving out a ring) as needed. Must not be
+    /// called on the first element.
+    fn retreat(&mut self, rows: usize, cols: usize) {
+        if self.offset > 0 {
+            self.offset -= 1;
+            return;
+        }
+        loop {
+            if self.direction == Direction::Right {
+                self.ring -= 1;
+            }
+            self.direction = self.direction.turn_back();
+            self.len = run_len(rows, cols, self.ring, self.direction);
+            if self.len > 0 {
+                self.offset = self.len - 1;
+                return;
+            }
+        }
+    }
+}
+
+/// The number of elements in one run of a ring, i.e. how many steps we take in `direction` before
+/// turning. Runs that would revisit an element (e.g. going back along a ring that is only a single
+/// row tall) are empty.
+fn run_len(rows: usize, cols: usize, ring: usize, direction: Direction) -> usize {
+    let height = rows - 2 * ring;
+    let width = cols - 2 * ring;
+    match direction {
+        Direction::Right => width,
+        Direction::Down => height - 1,
+        Direction::Left if height > 1 => width - 1,
+        Direction::Up if width > 1 => height.saturating_sub(2),
+        _ => 0,
+    }
+}
+
+/// Creates a matrix of size x size, with elements from 0 to size^2 - 1. See
+/// [`generator::counting`](crate::generator::counting) for rectangles, and an error instead of a
+/// panic for bad sizes.
+///
+/// # Arguments
+///
+/// * `size` - The width and length of the generated matrix
+///
+/// # Returns
+///
+/// A Vec<Vec<i32>> representing the matrix
+///
+/// # Panics
+///
+/// Panics if size is less than or equal to 0
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::spiral::create_matrix;
+///
+/// let matrix = create_matrix(3);
+/// assert_eq!(matrix, vec![
+///     vec![0, 1, 2],
+///     vec![3, 4, 5],
+///     vec![6, 7, 8],
+/// ]);
+/// ```
+pub fn create_matrix(size: i32) -> Vec<Vec<i32>> {
+    assert!(size > 0);
+
+    (0..size)
+        .map(|i| (0..size).map(|j| i * size + j).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+    use crate::traversal::{check_traversal, numbered};
+
+    const CORNERS: [Corner; 4] = [
+        Corner::TopLeft,
+        Corner::TopRight,
+        Corner::BottomRight,
+        Corner::BottomLeft,
+    ];
+    const ROTATIONS: [Rotation; 2] = [Rotation::Clockwise, Rotation::CounterClockwise];
+
+    /// Walks the matrix like a snake: go straight until the next cell is off the edge or already
+    /// visited, then turn. An outward spiral is the inward one with the other rotation, reversed.
+    fn brute_force(matrix: &[Vec<usize>], order: SpiralOrder) -> Vec<usize> {
+        if order.outward {
+            let inward = order.inward().rotation(order.rotation.opposite());
+            let mut result = brute_force(matrix, inward);
+            result.reverse();
+            return result;
+        }
+
+        let rows = matrix.len() as isize;
+        let cols = matrix.first().map_or(0, Vec::len) as isize;
+        let n = (rows * cols) as usize;
+        // right, down, left, up: turning clockwise is +1, counter-clockwise is +3
+        let deltas = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+        let turn = match order.rotation {
+            Rotation::Clockwise => 1,
+            Rotation::CounterClockwise => 3,
+        };
+        let (mut row, mut col, mut heading) = match (order.corner, order.rotation) {
+            (Corner::TopLeft, Rotation::Clockwise) => (0, 0, 0),
+            (Corner::TopLeft, Rotation::CounterClockwise) => (0, 0, 1),
+            (Corner::TopRight, Rotation::Clockwise) => (0, cols - 1, 1),
+            (Corner::TopRight, Rotation::CounterClockwise) => (0, cols - 1, 2),
+            (Corner::BottomRight, Rotation::Clockwise) => (rows - 1, cols - 1, 2),
+            (Corner::BottomRight, Rotation::CounterClockwise) => (rows - 1, cols - 1, 3),
+            (Corner::BottomLeft, Rotation::Clockwise) => (rows - 1, 0, 3),
+            (Corner::BottomLeft, Rotation::CounterClockwise) => (rows - 1, 0, 0),
+        };
+
+        let mut visited = vec![vec![false; cols as usize]; rows as usize];
+        let mut result = Vec::new();
+        for step in 0..n {
+            result.push(matrix[row as usize][col as usize]);
+            visited[row as usize][col as usize] = true;
+            if step + 1 == n {
+                break;
+            }
+            loop {
+                let (dr, dc) = deltas[heading];
+                let (r, c) = (row + dr, col + dc);
+                if (0..rows).contains(&r)
+                    && (0..cols).contains(&c)
+                    && !visited[r as usize][c as usize]
+                {
+                    (row, col) = (r, c);
+                    break;
+                }
+                heading = (heading + turn) % 4;
+            }
+        }
+        result
+    }
+
+    /// Every corner, rotation and direction.
+    fn all_orders() -> Vec<SpiralOrder> {
+        let mut orders = Vec::new();
+        for corner in CORNERS {
+            for rotation in ROTATIONS {
+                let order = SpiralOrder::new().corner(corner).rotation(rotation);
+                orders.push(order.inward());
+                orders.push(order.outward());
+            }
+        }
+        orders
+    }
+
+    #[test]
+    fn test_spiral1() {
+        let matrix1 = vec![
+            vec![1, 2, 3, 4],
+            vec![5, 6, 7, 8],
+            vec![9, 10, 11, 12],
+            vec![13, 14, 15, 16],
+        ];
+
+        assert_eq!(
+            spiral(&matrix1),
+            vec![1, 2, 3, 4, 8, 12, 16, 15, 14, 13, 9, 5, 6, 7, 11, 10]
+        );
+    }
+
+    #[test]
+    fn test_spiral2() {
+        let matrix2 = vec![
+            vec![1, 2, 3, 4, 5, 6],
+            vec![7, 8, 9, 10, 11, 12],
+            vec![13, 14, 15, 16, 17, 18],
+        ];
+
+        assert_eq!(
+            spiral(&matrix2),
+            vec![1, 2, 3, 4, 5, 6, 12, 18, 17, 16, 15, 14, 13, 7, 8, 9, 10, 11]
+        );
+    }
+
+    #[test]
+    fn test_spiral_3() {
+        let matrix3 = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+
+        assert_eq!(spiral(&matrix3), vec![1, 2, 3, 6, 9, 8, 7, 4, 5]);
+    }
+
+    #[test]
+    fn test_spiral_4() {
+        let matrix = vec![vec![1, 2, 3], vec![8, 9, 4], vec![7, 6, 5]];
+
+        assert_eq!(spiral(&matrix), vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_spiral_empty() {
+        let empty_matrix: Vec<Vec<i32>> = Vec::new();
+        assert_eq!(spiral(&empty_matrix), vec![]);
+    }
+
+    #[test]
+    fn test_spiral_single() {
+        assert_eq!(spiral(&[vec![1]]), vec![1]);
+    }
+
+    #[test]
+    fn test_spiral_float() {
+        let matrix = vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 5.0, 6.0],
+            vec![7.0, 8.0, 9.0],
+        ];
+
+        assert_eq!(
+            spiral(&matrix),
+            vec![1.0, 2.0, 3.0, 6.0, 9.0, 8.0, 7.0, 4.0, 5.0]
+        );
+    }
+
+    #[test]
+    fn test_spiral_empty_row() {
+        let matrix: Vec<Vec<i32>> = vec![vec![]];
+        assert_eq!(spiral(&matrix), vec![]);
+    }
+
+    #[test]
+    fn test_spiral_single_row_and_column() {
+        assert_eq!(spiral(&[vec![1, 2, 3, 4]]), vec![1, 2, 3, 4]);
+        assert_eq!(spiral(&[vec![1], vec![2], vec![3]]), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_spiral_iter_lazy() {
+        let matrix = create_matrix(5);
+        let first = SpiralIter::new(&matrix)
+            .take(6)
+            .copied()
+            .collect::<Vec<_>>();
+        assert_eq!(first, vec![0, 1, 2, 3, 4, 9]);
+    }
+
+    #[test]
+    fn test_spiral_iter_len() {
+        let matrix = create_matrix(4);
+        let mut iter = SpiralIter::new(&matrix);
+        assert_eq!(iter.len(), 16);
+        iter.next();
+        iter.next_back();
+        assert_eq!(iter.len(), 14);
+        assert_eq!(iter.by_ref().count(), 14);
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_spiral_iter_rev() {
+        for (rows, cols) in [
+            (1, 1),
+            (1, 5),
+            (5, 1),
+            (2, 3),
+            (3, 2),
+            (4, 4),
+            (5, 7),
+            (7, 5),
+        ] {
+            let matrix = numbered(rows, cols);
+            let mut forward = spiral(&matrix);
+            forward.reverse();
+            let backward = SpiralIter::new(&matrix).rev().copied().collect::<Vec<_>>();
+            assert_eq!(forward, backward, "{}x{}", rows, cols);
+        }
+    }
+
+    #[test]
+    fn test_spiral_iter_fold_after_next() {
+        let matrix = create_matrix(4);
+        let mut iter = SpiralIter::new(&matrix);
+        iter.nth(5);
+        iter.next_back();
+        let mut rest = Vec::new();
+        iter.for_each(|&x| rest.push(x));
+        assert_eq!(rest, vec![15, 14, 13, 12, 8, 4, 5, 6, 10]);
+    }
+
+    #[test]
+    fn test_spiral_iter_both_ends() {
+        let matrix = vec![vec![1, 2, 3], vec![8, 9, 4], vec![7, 6, 5]];
+        let mut iter = SpiralIter::new(&matrix);
+        let mut result = Vec::new();
+        while let Some(&x) = iter.next() {
+            result.push(x);
+            if let Some(&y) = iter.next_back() {
+                result.push(y);
+            }
+        }
+        assert_eq!(result, vec![1, 9, 2, 8, 3, 7, 4, 6, 5]);
+    }
+
+    #[test]
+    fn test_spiral_order_default() {
+        let matrix = numbered(4, 6);
+        assert_eq!(SpiralOrder::new().spiral(&matrix), spiral(&matrix));
+    }
+
+    #[test]
+    fn test_spiral_order_counter_clockwise() {
+        let matrix = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+        let order = SpiralOrder::new().rotation(Rotation::CounterClockwise);
+        assert_eq!(order.spiral(&matrix), vec![1, 4, 7, 8, 9, 6, 3, 2, 5]);
+    }
+
+    #[test]
+    fn test_spiral_order_corners() {
+        let matrix = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        let clockwise = |corner| SpiralOrder::new().corner(corner).spiral(&matrix);
+        assert_eq!(clockwise(Corner::TopLeft), vec![1, 2, 3, 6, 5, 4]);
+        assert_eq!(clockwise(Corner::TopRight), vec![3, 6, 5, 4, 1, 2]);
+        assert_eq!(clockwise(Corner::BottomRight), vec![6, 5, 4, 1, 2, 3]);
+        assert_eq!(clockwise(Corner::BottomLeft), vec![4, 1, 2, 3, 6, 5]);
+    }
+
+    #[test]
+    fn test_spiral_order_outward() {
+        let matrix = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+        let order = SpiralOrder::new().outward();
+        assert_eq!(order.spiral(&matrix), vec![5, 2, 3, 6, 9, 8, 7, 4, 1]);
+
+        let order = order.rotation(Rotation::CounterClockwise);
+        assert_eq!(order.spiral(&matrix), vec![5, 4, 7, 8, 9, 6, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_spiral_order_brute_force() {
+        for rows in 0..=7 {
+            for cols in 0..=7 {
+                let matrix = numbered(rows, if rows == 0 { 0 } else { cols });
+                for order in all_orders() {
+                    let expected = brute_force(&matrix, order);
+                    assert_eq!(order.spiral(&matrix), expected, "{rows}x{cols} {order:?}");
+
+                    // one element at a time, rather than through fold
+                    let mut iter = order.iter(&matrix);
+                    let stepped = std::iter::from_fn(|| iter.next().copied()).collect::<Vec<_>>();
+                    assert_eq!(stepped, expected, "{rows}x{cols} {order:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_spiral_order_rev() {
+        let matrix = numbered(5, 4);
+        for order in all_orders() {
+            let mut expected = brute_force(&matrix, order);
+            expected.reverse();
+
+            let mut folded = Vec::new();
+            order.iter(&matrix).rev().for_each(|&x| folded.push(x));
+            assert_eq!(folded, expected, "{order:?}");
+
+            // by value, copying the rows out as slices
+            let values = order.values(&matrix).rev().collect::<Vec<_>>();
+            assert_eq!(values, expected, "{order:?}");
+
+            let mut iter = order.iter(&matrix);
+            let stepped = std::iter::from_fn(|| iter.next_back().copied()).collect::<Vec<_>>();
+            assert_eq!(stepped, expected, "{order:?}");
+        }
+    }
+
+    #[test]
+    fn test_unspiral() {
+        let matrix = unspiral(&[1, 2, 3, 4, 5, 6, 7, 8, 9], 3, 3).unwrap();
+        assert_eq!(matrix, vec![vec![1, 2, 3], vec![8, 9, 4], vec![7, 6, 5]]);
+
+        let matrix = unspiral(&[1, 2, 3, 6, 5, 4], 2, 3).unwrap();
+        assert_eq!(matrix, vec![vec![1, 2, 3], vec![4, 5, 6]]);
+    }
+
+    #[test]
+    fn test_unspiral_round_trip() {
+        for rows in 0..=7 {
+            for cols in 0..=7 {
+                let matrix = numbered(rows, cols);
+                for order in all_orders() {
+                    let seq = order.spiral(&matrix);
+                    assert_eq!(
+                        order.unspiral(&seq, rows, cols).as_ref(),
+                        Ok(&matrix),
+                        "{rows}x{cols} {order:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_unspiral_wrong_length() {
+        assert_eq!(
+            unspiral(&[1, 2, 3], 2, 2),
+            Err(UnspiralError::LengthMismatch {
+                expected: 4,
+                actual: 3
+            })
+        );
+        assert_eq!(
+            unspiral(&[1, 2, 3, 4, 5], 2, 2),
+            Err(UnspiralError::LengthMismatch {
+                expected: 4,
+                actual: 5
+            })
+        );
+    }
+
+    #[test]
+    fn test_spiral_fill() {
+        let matrix = spiral_fill(3, 4, 1..).unwrap();
+        assert_eq!(
+            matrix,
+            vec![vec![1, 2, 3, 4], vec![10, 11, 12, 5], vec![9, 8, 7, 6]]
+        );
+
+        let strings = spiral_fill(2, 2, ["a", "b", "c", "d"].map(String::from)).unwrap();
+        assert_eq!(strings, vec![vec!["a", "b"], vec!["d", "c"]]);
+    }
+
+    #[test]
+    fn test_spiral_fill_too_short() {
+        assert_eq!(
+            spiral_fill(3, 3, 0..5),
+            Err(UnspiralError::LengthMismatch {
+                expected: 9,
+                actual: 5
+            })
+        );
+    }
+
+    #[test]
+    fn test_spiral_fill_leaves_rest() {
+        let mut values = 0..10;
+        spiral_fill(2, 2, values.by_ref()).unwrap();
+        assert_eq!(values.next(), Some(4));
+    }
+
+    #[test]
+    fn test_spiral_position_and_coord() {
+        for rows in 1..=9 {
+            for cols in 1..=9 {
+                let matrix = numbered(rows, cols);
+                for (pos, &x) in spiral(&matrix).iter().enumerate() {
+                    let (row, col) = (x / cols, x % cols);
+                    assert_eq!(spiral_position(rows, cols, row, col), pos, "{rows}x{cols}");
+                    assert_eq!(spiral_coord(rows, cols, pos), (row, col), "{rows}x{cols}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_spiral_position_and_coord_big() {
+        let (rows, cols) = (1000, 777);
+        let path = SpiralPath::new(rows, cols, SpiralOrder::new());
+        for (pos, (row, col)) in path.enumerate().step_by(997) {
+            assert_eq!(spiral_position(rows, cols, row, col), pos);
+            assert_eq!(spiral_coord(rows, cols, pos), (row, col));
+        }
+        assert_eq!(spiral_coord(rows, cols, rows * cols - 1), (611, 388));
+    }
+
+    #[test]
+    #[should_panic(expected = "row 2 has 2 columns, but the rows before it have 3")]
+    fn test_spiral_ragged() {
+        spiral(&vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_spiral_position_outside() {
+        spiral_position(3, 3, 3, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_spiral_coord_outside() {
+        spiral_coord(3, 3, 9);
+    }
+
+    #[test]
+    fn test_spiral_grid() {
+        let grid = Grid::from_iter(3, 4, 0..).unwrap();
+        assert_eq!(spiral(&grid), vec![0, 1, 2, 3, 7, 11, 10, 9, 8, 4, 5, 6]);
+
+        let nested = numbered(5, 3);
+        let grid = Grid::try_from(nested.as_slice()).unwrap();
+        for order in all_orders() {
+            assert_eq!(order.spiral(&grid), order.spiral(&nested), "{order:?}");
+        }
+    }
+
+    #[test]
+    fn test_spiral_fn() {
+        for (rows, cols) in [(0, 0), (1, 5), (5, 1), (4, 4), (3, 7), (8, 2)] {
+            let matrix = numbered(rows, cols);
+            let from_fn = spiral_fn(rows, cols, |row, col| row * cols + col);
+            assert_eq!(from_fn, spiral(&matrix), "{rows}x{cols}");
+        }
+    }
+
+    #[test]
+    fn test_spiral_values() {
+        let matrix = from_fn(5, 6, |row, col| format!("{row}{col}"));
+        for order in all_orders() {
+            let owned = order.values(&matrix).collect::<Vec<_>>();
+            let expected = order
+                .iter(&numbered(5, 6))
+                .map(|x| format!("{}{}", x / 6, x % 6))
+                .collect::<Vec<_>>();
+            assert_eq!(owned, expected, "{order:?}");
+
+            let mut backwards = order.values(&matrix).rev().collect::<Vec<_>>();
+            backwards.reverse();
+            assert_eq!(backwards, expected, "{order:?}");
+        }
+    }
+
+    #[test]
+    fn test_spiral_views() {
+        // 0  1  2  3  4  5
+        // 6  7  8  9  10 11
+        // 12 13 14 15 16 17
+        // 18 19 20 21 22 23
+        let matrix = numbered(4, 6);
+
+        let middle = matrix.sub_view(1..3, 1..5);
+        assert_eq!(spiral(&middle), vec![7, 8, 9, 10, 16, 15, 14, 13]);
+
+        let strided = matrix.sub_view(0..4, 0..6).strided(3, 2);
+        assert_eq!(spiral(&strided), vec![0, 2, 4, 22, 20, 18]);
+
+        // the counterclockwise spiral is the clockwise spiral of the transpose
+        let ccw = SpiralOrder::new().rotation(Rotation::CounterClockwise);
+        assert_eq!(spiral(&matrix.transposed()), ccw.spiral(&matrix));
+    }
+
+    #[test]
+    fn test_spiral_traversal() {
+        let matrix = numbered(4, 5);
+        for order in all_orders() {
+            let traversal = Spiral::with_order(4, 5, order);
+            assert_eq!(
+                traversal.traverse(&matrix),
+                order.spiral(&matrix),
+                "{order:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_spiral_is_traversal() {
+        for order in all_orders() {
+            check_traversal(8, |rows, cols| Spiral::with_order(rows, cols, order));
+        }
+    }
+
+    #[test]
+    fn test_spiral_strings() {
+        let matrix = vec![
+            vec![String::from("a"), String::from("b")],
+            vec![String::from("c"), String::from("d")],
+        ];
+        assert_eq!(spiral(&matrix), vec!["a", "b", "d", "c"]);
+        assert_eq!(
+            SpiralIter::new(&matrix).collect::<Vec<&String>>(),
+            vec!["a", "b", "d", "c"]
+        );
+    }
+
+    #[test]
+    fn test_spiral_mut() {
+        for rows in 0..=7 {
+            for cols in 0..=7 {
+                // numbering the cells along the spiral means the spiral is 0, 1, 2, ...
+                let mut matrix = vec![vec![usize::MAX; cols]; rows];
+                let iter = spiral_mut(&mut matrix);
+                assert_eq!(iter.len(), rows * cols);
+                for (cell, step) in iter.zip(0..) {
+                    *cell = step;
+                }
+                assert_eq!(
+                    spiral(&matrix),
+                    (0..rows * cols).collect::<Vec<_>>(),
+                    "{rows}x{cols}"
+                );
+
+                if rows > 0 && cols > 0 {
+                    let mut grid = Grid::from_flat(rows, cols, vec![0; rows * cols]).unwrap();
+                    spiral_mut(&mut grid)
+                        .zip(0..)
+                        .for_each(|(cell, step)| *cell = step);
+                    assert_eq!(grid.into_rows(), matrix);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_spiral_mut_strings() {
+        let mut matrix = vec![
+            vec![String::from("a"), String::from("b")],
+            vec![String::from("c"), String::from("d")],
+        ];
+        for (step, cell) in spiral_mut(&mut matrix).enumerate() {
+            cell.push_str(&step.to_string());
+        }
+        assert_eq!(matrix, vec![vec!["a0", "b1"], vec!["c3", "d2"]]);
+    }
+
+    #[test]
+    fn test_spiral_mega_matrix() {
+        let mega_matrix = create_matrix(1000);
+        assert_eq!(spiral(&mega_matrix).len(), 1000 * 1000);
+    }
+}
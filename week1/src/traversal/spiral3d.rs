@@ -0,0 +1,380 @@
+use super::rings::{ring_coord, ring_len};
+use super::{Spiral, Traversal};
+use crate::grid::RowMajor;
+
+/// Why a tensor can't be walked by [`spiral3d`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TensorError {
+    /// Layer `layer` had `actual` rows, but the layers before it had `expected`.
+    RaggedLayer {
+        layer: usize,
+        expected: usize,
+        actual: usize,
+    },
+    /// Row `row` of layer `layer` had `actual` columns, but the rows before it had `expected`.
+    RaggedRow {
+        layer: usize,
+        row: usize,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl std::fmt::Display for TensorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TensorError::RaggedLayer {
+                layer,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "layer {} has {} rows, but the layers before it have {}",
+                layer, actual, expected
+            ),
+            TensorError::RaggedRow {
+                layer,
+                row,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "row {} of layer {} has {} columns, but the rows before it have {}",
+                row, layer, actual, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TensorError {}
+
+/// The 3D version of [`spiral`](super::spiral::spiral): constructs a Vec<T> from a
+/// `[depth][rows][cols]` tensor by peeling it into shells, like a 2D spiral peels a matrix into
+/// rings, and walking the faces of each shell in a fixed order. Elements are cloned, like
+/// [`spiral`](super::spiral::spiral) does.
+///
+/// # Arguments
+///
+/// * `tensor` - A 3D tensor of T as a slice of layers, each a [`Grid`](crate::grid::Grid) or nested
+///   Vecs, indexed `tensor[layer][row][col]`. Every layer must have the same number of rows, and
+///   every row the same number of columns
+///
+/// # Returns
+///
+/// A Vec<T> with every element of the tensor exactly once, in the order of [`spiral3d_coords`], or
+/// a [`TensorError`] if the tensor is ragged
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::spiral3d::{spiral3d, TensorError};
+///
+/// let tensor = vec![
+///     vec![vec![1, 2], vec![3, 4]],
+///     vec![vec![5, 6], vec![7, 8]],
+/// ];
+///
+/// // the front layer as a spiral, then the back one
+/// assert_eq!(spiral3d(&tensor), Ok(vec![1, 2, 4, 3, 5, 6, 8, 7]));
+///
+/// let ragged = vec![vec![vec![1, 2], vec![3, 4]], vec![vec![5, 6]]];
+/// assert_eq!(
+///     spiral3d(&ragged),
+///     Err(TensorError::RaggedLayer { layer: 1, expected: 2, actual: 1 })
+/// );
+/// ```
+///
+/// # Time and Space Complexity
+///
+/// O(n) time, where n is the number of elements, and O(n) space for the result. Checking the shape
+/// first takes O(depth * rows) more.
+pub fn spiral3d<M>(tensor: &[M]) -> Result<Vec<M::Item>, TensorError>
+where
+    M: RowMajor,
+    M::Item: Clone,
+{
+    let depth = tensor.len();
+    let (rows, cols) = tensor.first().map_or((0, 0), |layer| layer.dims());
+    for (layer, matrix) in tensor.iter().enumerate() {
+        let (actual, _) = matrix.dims();
+        if actual != rows {
+            return Err(TensorError::RaggedLayer {
+                layer,
+                expected: rows,
+                actual,
+            });
+        }
+        // the width of nested Vecs comes from their first row, so every row has to be looked at
+        if let Some((row, actual)) = (0..rows)
+            .map(|row| matrix.row(row).len())
+            .enumerate()
+            .find(|&(_, len)| len != cols)
+        {
+            return Err(TensorError::RaggedRow {
+                layer,
+                row,
+                expected: cols,
+                actual,
+            });
+        }
+    }
+
+    Ok(spiral3d_coords(depth, rows, cols)
+        .map(|(layer, row, col)| tensor[layer].row(row)[col].clone())
+        .collect())
+}
+
+/// The number of shells a depth x rows x cols tensor peels into.
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::spiral3d::shell_count;
+///
+/// assert_eq!(shell_count(5, 8, 9), 3);
+/// assert_eq!(shell_count(1, 8, 9), 1);
+/// ```
+pub fn shell_count(depth: usize, rows: usize, cols: usize) -> usize {
+    depth.min(rows).min(cols).div_ceil(2)
+}
+
+/// The (layer, row, col) of every element of a depth x rows x cols tensor, in the order
+/// [`spiral3d`] visits them.
+///
+/// Shell k is everything at distance k from the closest face of the tensor. It's the surface of a
+/// smaller cuboid, which is walked in this order:
+///
+/// 1. The front face (its first layer), as a clockwise 2D [`spiral`](super::spiral::spiral) from
+///    its top left corner.
+/// 2. The four side faces, only for the layers strictly between the front and the back: top, right,
+///    bottom, then left, splitting the edges between them the same way the runs of a 2D spiral
+///    ring do. Each side face is walked layer by layer from front to back, and along each layer in
+///    the direction the 2D ring goes (left to right along the top, down the right, and so on).
+/// 3. The back face (its last layer), as a clockwise 2D spiral from its top left corner, if it's
+///    not the same layer as the front.
+///
+/// Degenerate shells fall out of this: a shell one layer deep is just its front face, and a shell
+/// one row tall or one column wide only has the side faces that aren't empty.
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::spiral3d::spiral3d_coords;
+///
+/// let coords = spiral3d_coords(3, 1, 2).collect::<Vec<_>>();
+/// assert_eq!(
+///     coords,
+///     vec![(0, 0, 0), (0, 0, 1), (1, 0, 0), (1, 0, 1), (2, 0, 0), (2, 0, 1)]
+/// );
+/// ```
+///
+/// # Time and Space Complexity
+///
+/// O(1) time per element and O(1) space.
+pub fn spiral3d_coords(
+    depth: usize,
+    rows: usize,
+    cols: usize,
+) -> impl Iterator<Item = (usize, usize, usize)> {
+    (0..shell_count(depth, rows, cols)).flat_map(move |shell| {
+        let (front, back) = (shell, depth - 1 - shell);
+        let (height, width) = (rows - 2 * shell, cols - 2 * shell);
+        let face = move |layer: usize| {
+            Spiral::new(height, width)
+                .coords()
+                .map(move |(row, col)| (layer, shell + row, shell + col))
+        };
+
+        // the positions along the shell's ring in every middle layer, split into the top, right,
+        // bottom and left runs
+        let lens = [
+            width,
+            height - 1,
+            if height > 1 { width - 1 } else { 0 },
+            if width > 1 {
+                height.saturating_sub(2)
+            } else {
+                0
+            },
+        ];
+        debug_assert_eq!(lens.iter().sum::<usize>(), ring_len(rows, cols, shell));
+        let mut start = 0;
+        let sides = lens.map(|len| {
+            start += len;
+            start - len..start
+        });
+        let middle = front + 1..back.max(front + 1);
+
+        let sides = sides.into_iter().flat_map(move |side| {
+            middle.clone().flat_map(move |layer| {
+                side.clone().map(move |pos| {
+                    let (row, col) = ring_coord(rows, cols, shell, pos);
+                    (layer, row, col)
+                })
+            })
+        });
+
+        let back_face = (back > front).then(|| face(back)).into_iter().flatten();
+        face(front).chain(sides).chain(back_face)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+    use crate::traversal::numbered;
+    use crate::traversal::spiral::spiral;
+
+    #[test]
+    fn test_spiral3d_coords_visit_every_cell() {
+        for depth in 0..=6 {
+            for rows in 0..=6 {
+                for cols in 0..=6 {
+                    let mut coords = spiral3d_coords(depth, rows, cols).collect::<Vec<_>>();
+                    coords.sort_unstable();
+                    let cells = (0..depth)
+                        .flat_map(|layer| {
+                            (0..rows)
+                                .flat_map(move |row| (0..cols).map(move |col| (layer, row, col)))
+                        })
+                        .collect::<Vec<_>>();
+                    assert_eq!(coords, cells, "{depth}x{rows}x{cols}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_spiral3d_follows_the_coords() {
+        // every layer is the same matrix, so each element says which row and column it came from
+        let tensor = vec![numbered(3, 4); 5];
+        let expected = spiral3d_coords(5, 3, 4)
+            .map(|(_, row, col)| row * 4 + col)
+            .collect::<Vec<_>>();
+        assert_eq!(spiral3d(&tensor), Ok(expected));
+    }
+
+    #[test]
+    fn test_spiral3d_single_layer_is_spiral() {
+        for (rows, cols) in [(1, 1), (1, 5), (5, 1), (4, 4), (3, 7)] {
+            let tensor = [numbered(rows, cols)];
+            assert_eq!(spiral3d(&tensor), Ok(spiral(&tensor[0])), "{rows}x{cols}");
+        }
+    }
+
+    #[test]
+    fn test_spiral3d_cube() {
+        let tensor = vec![
+            vec![vec![0, 1, 2], vec![3, 4, 5], vec![6, 7, 8]],
+            vec![vec![9, 10, 11], vec![12, 13, 14], vec![15, 16, 17]],
+            vec![vec![18, 19, 20], vec![21, 22, 23], vec![24, 25, 26]],
+        ];
+        let front = [0, 1, 2, 5, 8, 7, 6, 3, 4];
+        // the top, right, bottom and left sides of the middle layer
+        let sides = [9, 10, 11, 14, 17, 16, 15, 12];
+        let back = [18, 19, 20, 23, 26, 25, 24, 21, 22];
+        // the center is a shell of its own
+        let center = [13];
+        assert_eq!(
+            spiral3d(&tensor),
+            Ok([&front[..], &sides, &back, &center].concat())
+        );
+    }
+
+    #[test]
+    fn test_spiral3d_sides_go_face_by_face() {
+        // 4 layers of 2x2: the side faces of the two middle layers are walked face by face
+        let tensor = vec![
+            vec![vec![0, 1], vec![2, 3]],
+            vec![vec![4, 5], vec![6, 7]],
+            vec![vec![8, 9], vec![10, 11]],
+            vec![vec![12, 13], vec![14, 15]],
+        ];
+        assert_eq!(
+            spiral3d(&tensor),
+            Ok(vec![0, 1, 3, 2, 4, 5, 8, 9, 7, 11, 6, 10, 12, 13, 15, 14])
+        );
+    }
+
+    #[test]
+    fn test_spiral3d_grid_layers() {
+        // layers can be Grids, and the elements only need to be Clone
+        let nested = (0..3)
+            .map(|layer| {
+                (0..2)
+                    .map(|row| (0..2).map(|col| format!("{layer}{row}{col}")).collect())
+                    .collect::<Vec<Vec<_>>>()
+            })
+            .collect::<Vec<_>>();
+        let layers = nested
+            .iter()
+            .map(|layer| Grid::from_rows(layer.clone()).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(spiral3d(&layers), spiral3d(&nested));
+        assert_eq!(
+            spiral3d(&layers).unwrap()[..4],
+            ["000", "001", "011", "010"]
+        );
+    }
+
+    #[test]
+    fn test_spiral3d_empty() {
+        assert_eq!(spiral3d::<Vec<Vec<i32>>>(&[]), Ok(vec![]));
+        assert_eq!(spiral3d(&vec![numbered(0, 4); 3]), Ok(vec![]));
+        assert_eq!(spiral3d(&vec![numbered(4, 0); 3]), Ok(vec![]));
+        assert_eq!(shell_count(0, 4, 4), 0);
+    }
+
+    #[test]
+    fn test_spiral3d_ragged_layer() {
+        let mut tensor = vec![numbered(2, 2); 3];
+        tensor[1].pop();
+        let err = spiral3d(&tensor).unwrap_err();
+        assert_eq!(
+            err,
+            TensorError::RaggedLayer {
+                layer: 1,
+                expected: 2,
+                actual: 1
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "layer 1 has 1 rows, but the layers before it have 2"
+        );
+    }
+
+    #[test]
+    fn test_spiral3d_ragged_row() {
+        // the coordinates never reach the extra column, so only the check can catch this
+        let mut tensor = vec![numbered(2, 2); 3];
+        tensor[2][1].push(99);
+        let err = spiral3d(&tensor).unwrap_err();
+        assert_eq!(
+            err,
+            TensorError::RaggedRow {
+                layer: 2,
+                row: 1,
+                expected: 2,
+                actual: 3
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "row 1 of layer 2 has 3 columns, but the rows before it have 2"
+        );
+    }
+
+    #[test]
+    fn test_spiral3d_coords_len() {
+        for shape in [(2, 3, 4), (7, 1, 9), (5, 5, 5), (1, 1, 1)] {
+            let (depth, rows, cols) = shape;
+            assert_eq!(
+                spiral3d_coords(depth, rows, cols).count(),
+                depth * rows * cols,
+                "{shape:?}"
+            );
+        }
+    }
+}
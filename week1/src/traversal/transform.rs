@@ -0,0 +1,455 @@
+use crate::grid::RowMajorMut;
+
+/// How far to rotate a matrix, clockwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Angle {
+    Deg90,
+    Deg180,
+    Deg270,
+}
+
+impl Angle {
+    /// The rotation that undoes this one.
+    pub fn inverse(self) -> Angle {
+        match self {
+            Angle::Deg90 => Angle::Deg270,
+            Angle::Deg180 => Angle::Deg180,
+            Angle::Deg270 => Angle::Deg90,
+        }
+    }
+}
+
+/// Transposes a square matrix in place, so the element at (row, col) ends up at (col, row).
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::transform::transpose;
+///
+/// let mut matrix = vec![vec![1, 2], vec![3, 4]];
+/// transpose(&mut matrix);
+/// assert_eq!(matrix, vec![vec![1, 3], vec![2, 4]]);
+/// ```
+///
+/// # Time and Space Complexity
+///
+/// O(n) time, where n is the number of elements, since everything off the diagonal is swapped
+/// once, and O(1) space.
+///
+/// # Panics
+///
+/// Panics if the matrix isn't square. A rectangular matrix changes shape when it's transposed, so
+/// use [`transpose_flat`] or [`Grid::transpose`](crate::grid::Grid::transpose) for those.
+pub fn transpose<M: RowMajorMut + ?Sized>(matrix: &mut M) {
+    let size = square(matrix, "transpose");
+    for row in 0..size {
+        for col in row + 1..size {
+            matrix.swap((row, col), (col, row));
+        }
+    }
+}
+
+/// Rotates a matrix clockwise in place.
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::transform::{rotate, Angle};
+///
+/// let mut matrix = vec![vec![1, 2], vec![3, 4]];
+/// rotate(&mut matrix, Angle::Deg90);
+/// assert_eq!(matrix, vec![vec![3, 1], vec![4, 2]]);
+/// ```
+///
+/// # Approach
+///
+/// A quarter turn clockwise is a transpose followed by a horizontal flip, and a quarter turn the
+/// other way is a transpose followed by a vertical flip. A half turn is both flips, which doesn't
+/// change the shape, so that one works on any matrix.
+///
+/// # Time and Space Complexity
+///
+/// O(n) time, where n is the number of elements, and O(1) space.
+///
+/// # Panics
+///
+/// Panics if the angle is 90 or 270 degrees and the matrix isn't square. Use [`rotate_flat`] or
+/// [`Grid::rotate`](crate::grid::Grid::rotate) for those.
+pub fn rotate<M: RowMajorMut + ?Sized>(matrix: &mut M, angle: Angle) {
+    match angle {
+        Angle::Deg90 => {
+            square(matrix, "rotate 90 degrees");
+            transpose(matrix);
+            flip_horizontal(matrix);
+        }
+        Angle::Deg180 => {
+            flip_horizontal(matrix);
+            flip_vertical(matrix);
+        }
+        Angle::Deg270 => {
+            square(matrix, "rotate 270 degrees");
+            transpose(matrix);
+            flip_vertical(matrix);
+        }
+    }
+}
+
+/// Mirrors a matrix left to right in place, reversing every row.
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::transform::flip_horizontal;
+///
+/// let mut matrix = vec![vec![1, 2, 3], vec![4, 5, 6]];
+/// flip_horizontal(&mut matrix);
+/// assert_eq!(matrix, vec![vec![3, 2, 1], vec![6, 5, 4]]);
+/// ```
+pub fn flip_horizontal<M: RowMajorMut + ?Sized>(matrix: &mut M) {
+    for row in matrix.rows_mut() {
+        row.reverse();
+    }
+}
+
+/// Mirrors a matrix top to bottom in place, reversing the order of the rows.
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::transform::flip_vertical;
+///
+/// let mut matrix = vec![vec![1, 2, 3], vec![4, 5, 6]];
+/// flip_vertical(&mut matrix);
+/// assert_eq!(matrix, vec![vec![4, 5, 6], vec![1, 2, 3]]);
+/// ```
+pub fn flip_vertical<M: RowMajorMut + ?Sized>(matrix: &mut M) {
+    let mut rows = matrix.rows_mut();
+    let height = rows.len();
+    for row in 0..height / 2 {
+        let (upper, lower) = rows.split_at_mut(height - 1 - row);
+        upper[row].swap_with_slice(lower[0]);
+    }
+}
+
+/// Transposes a rows x cols matrix stored as a flat row-major buffer, in place. Afterwards the
+/// buffer holds the cols x rows transpose, also row-major.
+///
+/// # Arguments
+///
+/// * `data` - The elements of the matrix, row by row
+/// * `rows` - The number of rows in the matrix
+/// * `cols` - The number of columns in the matrix
+///
+/// # Returns
+///
+/// The (rows, cols) of the transposed matrix
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::transform::transpose_flat;
+///
+/// let mut data = [1, 2, 3, 4, 5, 6];
+/// assert_eq!(transpose_flat(&mut data, 2, 3), (3, 2));
+/// assert_eq!(data, [1, 4, 2, 5, 3, 6]);
+/// ```
+///
+/// # Approach
+///
+/// The element at index i = row * cols + col moves to col * rows + row. That's a permutation of
+/// the buffer, so it's made of cycles, and each cycle can be rotated into place with swaps by
+/// following it from any element in it until it comes back around. A bit per element remembers
+/// which ones have already been moved, so each cycle is only followed once.
+///
+/// # Time and Space Complexity
+///
+/// O(n) time, where n is rows * cols, since every element is swapped at most once. The space
+/// complexity is O(n) bits for the bookkeeping, which is a lot less than a copy of the matrix.
+///
+/// # Panics
+///
+/// Panics if `data` doesn't have exactly rows * cols elements.
+pub fn transpose_flat<T>(data: &mut [T], rows: usize, cols: usize) -> (usize, usize) {
+    check_len(data, rows, cols);
+    permute(data, |i| (i % cols) * rows + i / cols);
+    (cols, rows)
+}
+
+/// Rotates a rows x cols matrix stored as a flat row-major buffer clockwise, in place. A quarter
+/// turn either way swaps the number of rows and columns.
+///
+/// # Returns
+///
+/// The (rows, cols) of the rotated matrix
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::transform::{rotate_flat, Angle};
+///
+/// // 1 2 3      4 1
+/// // 4 5 6  ->  5 2
+/// //            6 3
+/// let mut data = [1, 2, 3, 4, 5, 6];
+/// assert_eq!(rotate_flat(&mut data, 2, 3, Angle::Deg90), (3, 2));
+/// assert_eq!(data, [4, 1, 5, 2, 6, 3]);
+/// ```
+///
+/// # Approach
+///
+/// Same as [`transpose_flat`], with the permutation each rotation makes. A half turn is just
+/// reversing the buffer.
+///
+/// # Time and Space Complexity
+///
+/// O(n) time, where n is rows * cols, and O(n) bits of space for quarter turns, O(1) for half
+/// turns.
+///
+/// # Panics
+///
+/// Panics if `data` doesn't have exactly rows * cols elements.
+pub fn rotate_flat<T>(data: &mut [T], rows: usize, cols: usize, angle: Angle) -> (usize, usize) {
+    check_len(data, rows, cols);
+    match angle {
+        // (row, col) -> (col, rows - 1 - row) in a cols x rows matrix
+        Angle::Deg90 => {
+            permute(data, |i| (i % cols) * rows + rows - 1 - i / cols);
+            (cols, rows)
+        }
+        Angle::Deg180 => {
+            data.reverse();
+            (rows, cols)
+        }
+        // (row, col) -> (cols - 1 - col, row) in a cols x rows matrix
+        Angle::Deg270 => {
+            permute(data, |i| (cols - 1 - i % cols) * rows + i / cols);
+            (cols, rows)
+        }
+    }
+}
+
+/// Mirrors a rows x cols matrix stored as a flat row-major buffer left to right, in place.
+///
+/// # Panics
+///
+/// Panics if `data` doesn't have exactly rows * cols elements.
+pub fn flip_horizontal_flat<T>(data: &mut [T], rows: usize, cols: usize) {
+    check_len(data, rows, cols);
+    if cols > 0 {
+        data.chunks_mut(cols).for_each(<[T]>::reverse);
+    }
+}
+
+/// Mirrors a rows x cols matrix stored as a flat row-major buffer top to bottom, in place.
+///
+/// # Panics
+///
+/// Panics if `data` doesn't have exactly rows * cols elements.
+pub fn flip_vertical_flat<T>(data: &mut [T], rows: usize, cols: usize) {
+    check_len(data, rows, cols);
+    for row in 0..rows / 2 {
+        let (upper, lower) = data.split_at_mut((rows - 1 - row) * cols);
+        upper[row * cols..(row + 1) * cols].swap_with_slice(&mut lower[..cols]);
+    }
+}
+
+/// Moves the element at every index i of `data` to `dest(i)`, where `dest` is a permutation, by
+/// following its cycles.
+fn permute<T>(data: &mut [T], dest: impl Fn(usize) -> usize) {
+    let mut moved = vec![0u64; data.len().div_ceil(64)];
+    for start in 0..data.len() {
+        if moved[start / 64] & (1 << (start % 64)) != 0 {
+            continue;
+        }
+        // the element that belongs at `next` is always sitting at `start`, so swapping it over
+        // puts it in place and brings the next one around
+        let mut next = dest(start);
+        while next != start {
+            data.swap(start, next);
+            moved[next / 64] |= 1 << (next % 64);
+            next = dest(next);
+        }
+        moved[start / 64] |= 1 << (start % 64);
+    }
+}
+
+/// The side of a square matrix, or a panic explaining that `what` needs one.
+fn square<M: RowMajorMut + ?Sized>(matrix: &M, what: &str) -> usize {
+    let (rows, cols) = matrix.dims();
+    assert_eq!(
+        rows, cols,
+        "can only {} a square matrix in place, not a {}x{} one",
+        what, rows, cols
+    );
+    rows
+}
+
+fn check_len<T>(data: &[T], rows: usize, cols: usize) {
+    assert_eq!(
+        data.len(),
+        rows * cols,
+        "a {}x{} matrix has {} elements, not {}",
+        rows,
+        cols,
+        rows * cols,
+        data.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::counting;
+    use crate::grid::Grid;
+    use crate::traversal::numbered;
+    use crate::traversal::spiral::{spiral, Corner, Rotation, SpiralOrder};
+
+    /// Rotates a flat rows x cols matrix a quarter turn by building a new one, to check the
+    /// in-place versions against.
+    fn rotated(data: &[usize], rows: usize, cols: usize) -> Vec<usize> {
+        (0..cols)
+            .flat_map(|row| (0..rows).map(move |col| data[(rows - 1 - col) * cols + row]))
+            .collect()
+    }
+
+    #[test]
+    fn test_transpose_square() {
+        let mut matrix = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+        transpose(&mut matrix);
+        assert_eq!(matrix, vec![vec![1, 4, 7], vec![2, 5, 8], vec![3, 6, 9]]);
+        transpose(&mut matrix);
+        assert_eq!(matrix, vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]);
+    }
+
+    #[test]
+    fn test_rotate_square() {
+        let original = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+        let mut matrix = original.clone();
+        rotate(&mut matrix, Angle::Deg90);
+        assert_eq!(matrix, vec![vec![7, 4, 1], vec![8, 5, 2], vec![9, 6, 3]]);
+        rotate(&mut matrix, Angle::Deg180);
+        assert_eq!(matrix, vec![vec![3, 6, 9], vec![2, 5, 8], vec![1, 4, 7]]);
+        rotate(&mut matrix, Angle::Deg90);
+        assert_eq!(matrix, original);
+
+        for angle in [Angle::Deg90, Angle::Deg180, Angle::Deg270] {
+            rotate(&mut matrix, angle);
+            rotate(&mut matrix, angle.inverse());
+            assert_eq!(matrix, original, "{angle:?}");
+        }
+    }
+
+    #[test]
+    fn test_rotate_half_turn_any_shape() {
+        let mut matrix = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        rotate(&mut matrix, Angle::Deg180);
+        assert_eq!(matrix, vec![vec![6, 5, 4], vec![3, 2, 1]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "can only rotate 90 degrees a square matrix in place, not a 2x3 one")]
+    fn test_rotate_not_square() {
+        rotate(&mut vec![vec![1, 2, 3], vec![4, 5, 6]], Angle::Deg90);
+    }
+
+    #[test]
+    fn test_flat_matches_rebuilt() {
+        for rows in 0..=6 {
+            for cols in 0..=6 {
+                let matrix = (0..rows * cols).collect::<Vec<_>>();
+                let quarter = rotated(&matrix, rows, cols);
+                let half = rotated(&quarter, cols, rows);
+                let three_quarters = rotated(&half, rows, cols);
+
+                for (angle, expected, dims) in [
+                    (Angle::Deg90, &quarter, (cols, rows)),
+                    (Angle::Deg180, &half, (rows, cols)),
+                    (Angle::Deg270, &three_quarters, (cols, rows)),
+                ] {
+                    let mut data = matrix.clone();
+                    assert_eq!(rotate_flat(&mut data, rows, cols, angle), dims);
+                    assert_eq!(&data, expected, "{rows}x{cols} {angle:?}");
+                }
+
+                let mut data = matrix;
+                assert_eq!(transpose_flat(&mut data, rows, cols), (cols, rows));
+                let expected = (0..cols)
+                    .flat_map(|row| (0..rows).map(move |col| col * cols + row))
+                    .collect::<Vec<_>>();
+                assert_eq!(data, expected, "{rows}x{cols}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_flips() {
+        let mut data = [1, 2, 3, 4, 5, 6];
+        flip_horizontal_flat(&mut data, 2, 3);
+        assert_eq!(data, [3, 2, 1, 6, 5, 4]);
+        flip_vertical_flat(&mut data, 2, 3);
+        assert_eq!(data, [6, 5, 4, 3, 2, 1]);
+
+        // flipping both ways is a half turn, on nested Vecs and flat buffers alike
+        let mut matrix = numbered(3, 4);
+        flip_horizontal(&mut matrix);
+        flip_vertical(&mut matrix);
+        let mut data = (0..12).collect::<Vec<_>>();
+        rotate_flat(&mut data, 3, 4, Angle::Deg180);
+        assert_eq!(matrix.concat(), data);
+    }
+
+    #[test]
+    fn test_grid_transforms() {
+        let mut grid = counting(2, 3).unwrap();
+        grid.transpose();
+        assert_eq!(grid.dims(), (3, 2));
+        assert_eq!(grid.as_slice(), &[0, 3, 1, 4, 2, 5]);
+
+        let mut grid = counting(2, 3).unwrap();
+        grid.rotate(Angle::Deg270);
+        assert_eq!(grid.into_rows(), vec![vec![2, 5], vec![1, 4], vec![0, 3]]);
+
+        // there's no such thing as a 3x0 grid
+        let mut empty = Grid::<usize>::from_flat(0, 3, Vec::new()).unwrap();
+        empty.transpose();
+        assert_eq!(empty.dims(), (0, 0));
+    }
+
+    #[test]
+    fn test_spiral_identities() {
+        for rows in 1..=6 {
+            for cols in 1..=6 {
+                let matrix = counting(rows, cols).unwrap();
+                let shape = format!("{rows}x{cols}");
+
+                // counter-clockwise is the clockwise spiral of the transpose
+                let mut transposed = matrix.clone();
+                transposed.transpose();
+                let counter_clockwise = SpiralOrder::new().rotation(Rotation::CounterClockwise);
+                assert_eq!(
+                    counter_clockwise.spiral(&matrix),
+                    spiral(&transposed),
+                    "{shape}"
+                );
+
+                // a quarter turn clockwise brings the bottom left corner to the top left
+                let mut quarter = matrix.clone();
+                quarter.rotate(Angle::Deg90);
+                let bottom_left = SpiralOrder::new().corner(Corner::BottomLeft);
+                assert_eq!(bottom_left.spiral(&matrix), spiral(&quarter), "{shape}");
+
+                // mirroring swaps the corners on the left and right, and which way it turns
+                let mut mirrored = matrix.clone();
+                flip_horizontal(&mut mirrored);
+                let top_right = counter_clockwise.corner(Corner::TopRight);
+                assert_eq!(top_right.spiral(&matrix), spiral(&mirrored), "{shape}");
+
+                // and a half turn makes it start from the opposite corner
+                let mut half = matrix.clone();
+                half.rotate(Angle::Deg180);
+                let bottom_right = SpiralOrder::new().corner(Corner::BottomRight);
+                assert_eq!(bottom_right.spiral(&matrix), spiral(&half), "{shape}");
+            }
+        }
+    }
+}
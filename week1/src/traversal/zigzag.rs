@@ -0,0 +1,159 @@
+use super::Traversal;
+
+/// Walks a matrix one anti-diagonal at a time, alternating direction, like a JPEG encoder walks
+/// an 8x8 block: right along the top, then down-left along the next diagonal, up-right along the
+/// one after that, and so on. Works for any rectangle, the diagonals just get cut short.
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::{Traversal, Zigzag};
+///
+/// // 1 2 6
+/// // 3 5 7
+/// // 4 8 9
+/// let matrix = vec![vec![1, 2, 6], vec![3, 5, 7], vec![4, 8, 9]];
+/// assert_eq!(
+///     Zigzag::new(3, 3).traverse(&matrix),
+///     vec![1, 2, 3, 4, 5, 6, 7, 8, 9]
+/// );
+/// ```
+///
+/// # Approach
+///
+/// Cell (row, col) is on diagonal row + col. Odd diagonals go down (row increasing) and even
+/// diagonals go up. The number of cells on the diagonals before diagonal d is the number of
+/// cells with row + col < d, which is a triangle number with the parts that hang off the bottom
+/// and right of the matrix cut off (inclusion-exclusion: T(d) - T(d - rows) - T(d - cols) +
+/// T(d - rows - cols)). So `position` is O(1), and `coord` binary searches for the diagonal.
+///
+/// # Time and Space Complexity
+///
+/// `position` is O(1) and `coord` is O(log(rows + cols)) time. Both are O(1) space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Zigzag {
+    rows: usize,
+    cols: usize,
+}
+
+impl Zigzag {
+    /// The zigzag order of a rows x cols matrix.
+    pub fn new(rows: usize, cols: usize) -> Zigzag {
+        Zigzag { rows, cols }
+    }
+
+    /// The number of cells with row + col < diagonal.
+    fn cells_before(&self, diagonal: usize) -> usize {
+        let triangle = |n: usize| n * (n + 1) / 2;
+        triangle(diagonal)
+            - triangle(diagonal.saturating_sub(self.rows))
+            - triangle(diagonal.saturating_sub(self.cols))
+            + triangle(diagonal.saturating_sub(self.rows + self.cols))
+    }
+
+    /// The first and last row on a diagonal.
+    fn row_range(&self, diagonal: usize) -> (usize, usize) {
+        (
+            diagonal.saturating_sub(self.cols - 1),
+            diagonal.min(self.rows - 1),
+        )
+    }
+}
+
+impl Traversal for Zigzag {
+    fn dims(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    fn coord(&self, pos: usize) -> (usize, usize) {
+        assert!(
+            pos < self.len(),
+            "position {} is outside of the matrix",
+            pos
+        );
+
+        // the last diagonal with at most pos cells before it
+        let (mut low, mut high) = (0, self.rows + self.cols - 1);
+        while high - low > 1 {
+            let mid = (low + high) / 2;
+            if self.cells_before(mid) <= pos {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        let diagonal = low;
+
+        let offset = pos - self.cells_before(diagonal);
+        let (first, last) = self.row_range(diagonal);
+        let row = if diagonal % 2 == 1 {
+            first + offset
+        } else {
+            last - offset
+        };
+        (row, diagonal - row)
+    }
+
+    fn position(&self, row: usize, col: usize) -> usize {
+        assert!(
+            row < self.rows && col < self.cols,
+            "({}, {}) is outside of the matrix",
+            row,
+            col
+        );
+        let diagonal = row + col;
+        let (first, last) = self.row_range(diagonal);
+        let offset = if diagonal % 2 == 1 {
+            row - first
+        } else {
+            last - row
+        };
+        self.cells_before(diagonal) + offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traversal::check_traversal;
+
+    #[test]
+    fn test_zigzag_jpeg() {
+        // the standard JPEG zigzag for the top left of an 8x8 block
+        let zigzag = Zigzag::new(8, 8);
+        let start = zigzag.coords().take(10).collect::<Vec<_>>();
+        assert_eq!(
+            start,
+            vec![
+                (0, 0),
+                (0, 1),
+                (1, 0),
+                (2, 0),
+                (1, 1),
+                (0, 2),
+                (0, 3),
+                (1, 2),
+                (2, 1),
+                (3, 0)
+            ]
+        );
+        assert_eq!(zigzag.coord(63), (7, 7));
+    }
+
+    #[test]
+    fn test_zigzag_rectangle() {
+        // 1  2  6  7
+        // 3  5  8  11
+        // 4  9  10 12
+        let matrix = vec![vec![1, 2, 6, 7], vec![3, 5, 8, 11], vec![4, 9, 10, 12]];
+        assert_eq!(
+            Zigzag::new(3, 4).traverse(&matrix),
+            (1..=12).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_zigzag_is_traversal() {
+        check_traversal(9, Zigzag::new);
+    }
+}
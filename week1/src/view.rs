@@ -0,0 +1,414 @@
+use std::ops::Range;
+
+use crate::grid::{GridError, RowMajor};
+
+/// A read-only view of a 2D matrix that can be asked for any single element, without saying
+/// anything about how (or whether) the elements are stored. Elements are handed out by value, so
+/// a view can just as well compute them on the fly, see [`from_fn`].
+///
+/// Every [`RowMajor`] matrix whose elements are Clone is a view of itself, and views can be
+/// wrapped in [`SubView`]s and [`Transposed`] views, which never copy anything.
+///
+/// # Example
+///
+/// ```
+/// use week1::view::{from_fn, MatrixView};
+///
+/// let matrix = vec![vec![1, 2, 3], vec![4, 5, 6]];
+/// // Vec has a `get` of its own, so ask for the MatrixView one
+/// assert_eq!(MatrixView::get(&matrix, 1, 0), 4);
+///
+/// let transposed = matrix.transposed();
+/// assert_eq!(transposed.dims(), (3, 2));
+/// assert_eq!(transposed.get(0, 1), 4);
+///
+/// let multiplication = from_fn(10, 10, |row, col| (row + 1) * (col + 1));
+/// assert_eq!(multiplication.get(6, 7), 56);
+/// ```
+pub trait MatrixView {
+    type Item;
+
+    /// The (rows, cols) of the matrix.
+    fn dims(&self) -> (usize, usize);
+
+    /// The element at (row, col).
+    ///
+    /// # Panics
+    ///
+    /// May panic if (row, col) is outside of the matrix.
+    fn get(&self, row: usize, col: usize) -> Self::Item;
+
+    /// Feeds the elements of `row` in the columns `cols` to `f`, from left to right. Walks that go
+    /// along rows use this, so matrices that store their rows as slices can copy straight out of
+    /// them. Everything else calls [`get`](MatrixView::get) for each element.
+    fn fold_row<B, F>(&self, row: usize, cols: Range<usize>, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        cols.fold(init, |acc, col| f(acc, self.get(row, col)))
+    }
+
+    /// The same as [`fold_row`](MatrixView::fold_row), from right to left.
+    fn rfold_row<B, F>(&self, row: usize, cols: Range<usize>, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        cols.rev().fold(init, |acc, col| f(acc, self.get(row, col)))
+    }
+
+    /// Checks that the matrix really has the shape [`dims`](MatrixView::dims) says, which can only
+    /// go wrong for nested Vecs, see [`RowMajor::check_rows`]. Views that compute their elements
+    /// are always fine.
+    fn check_shape(&self) -> Result<(), GridError> {
+        Ok(())
+    }
+
+    /// The rows and columns of this view that fall in the given ranges. See [`SubView`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if a range goes past the edge of the matrix.
+    fn sub_view(&self, rows: Range<usize>, cols: Range<usize>) -> SubView<'_, Self> {
+        SubView::new(self, rows, cols)
+    }
+
+    /// This view with its rows and columns swapped. See [`Transposed`].
+    fn transposed(&self) -> Transposed<'_, Self> {
+        Transposed { view: self }
+    }
+}
+
+impl<M> MatrixView for M
+where
+    M: RowMajor + ?Sized,
+    M::Item: Clone,
+{
+    type Item = M::Item;
+
+    fn dims(&self) -> (usize, usize) {
+        RowMajor::dims(self)
+    }
+
+    fn get(&self, row: usize, col: usize) -> M::Item {
+        self.row(row)[col].clone()
+    }
+
+    fn check_shape(&self) -> Result<(), GridError> {
+        RowMajor::check_rows(self)
+    }
+
+    fn fold_row<B, F>(&self, row: usize, cols: Range<usize>, init: B, f: F) -> B
+    where
+        F: FnMut(B, M::Item) -> B,
+    {
+        self.row(row)[cols].iter().cloned().fold(init, f)
+    }
+
+    fn rfold_row<B, F>(&self, row: usize, cols: Range<usize>, init: B, f: F) -> B
+    where
+        F: FnMut(B, M::Item) -> B,
+    {
+        self.row(row)[cols].iter().rev().cloned().fold(init, f)
+    }
+}
+
+/// Makes a rows x cols matrix whose element at (row, col) is `f(row, col)`. Nothing is stored, `f`
+/// is called again every time an element is looked at.
+///
+/// # Arguments
+///
+/// * `rows` - The number of rows in the matrix
+/// * `cols` - The number of columns in the matrix
+/// * `f` - Computes the element at (row, col)
+///
+/// # Example
+///
+/// ```
+/// use week1::view::{from_fn, MatrixView};
+///
+/// // a 100000 x 100000 matrix that takes up no memory at all
+/// let distances = from_fn(100_000, 100_000, |row, col| row.abs_diff(col));
+/// assert_eq!(distances.get(3, 99_999), 99_996);
+/// ```
+pub fn from_fn<T, F>(rows: usize, cols: usize, f: F) -> FnMatrix<F>
+where
+    F: Fn(usize, usize) -> T,
+{
+    FnMatrix { rows, cols, f }
+}
+
+/// A matrix defined by a function of its coordinates. See [`from_fn`].
+#[derive(Clone, Copy)]
+pub struct FnMatrix<F> {
+    rows: usize,
+    cols: usize,
+    f: F,
+}
+
+impl<T, F> MatrixView for FnMatrix<F>
+where
+    F: Fn(usize, usize) -> T,
+{
+    type Item = T;
+
+    fn dims(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    fn get(&self, row: usize, col: usize) -> T {
+        assert!(
+            row < self.rows && col < self.cols,
+            "({}, {}) is outside of a {}x{} matrix",
+            row,
+            col,
+            self.rows,
+            self.cols
+        );
+        (self.f)(row, col)
+    }
+}
+
+/// A window into a bigger matrix: the rows and columns in some ranges, optionally only every
+/// `row_step`th row and `col_step`th column of them. Element (row, col) of the sub-view is element
+/// (rows.start + row * row_step, cols.start + col * col_step) of the matrix underneath.
+///
+/// # Example
+///
+/// ```
+/// use week1::view::{from_fn, MatrixView};
+///
+/// //  0  1  2  3  4
+/// //  5  6  7  8  9
+/// // 10 11 12 13 14
+/// // 15 16 17 18 19
+/// let matrix = from_fn(4, 5, |row, col| row * 5 + col);
+///
+/// let middle = matrix.sub_view(1..3, 1..4);
+/// assert_eq!(middle.dims(), (2, 3));
+/// assert_eq!(middle.get(1, 0), 11);
+///
+/// // every other column of every other row
+/// let corners = matrix.sub_view(0..4, 0..5).strided(2, 2);
+/// assert_eq!(corners.dims(), (2, 3));
+/// assert_eq!(corners.get(1, 2), 14);
+/// ```
+pub struct SubView<'a, V: ?Sized> {
+    view: &'a V,
+    top: usize,
+    left: usize,
+    rows: usize,
+    cols: usize,
+    row_step: usize,
+    col_step: usize,
+}
+
+impl<'a, V: MatrixView + ?Sized> SubView<'a, V> {
+    fn new(view: &'a V, rows: Range<usize>, cols: Range<usize>) -> SubView<'a, V> {
+        let (height, width) = view.dims();
+        assert!(
+            rows.start <= rows.end && rows.end <= height,
+            "rows {:?} are outside of a matrix with {} rows",
+            rows,
+            height
+        );
+        assert!(
+            cols.start <= cols.end && cols.end <= width,
+            "columns {:?} are outside of a matrix with {} columns",
+            cols,
+            width
+        );
+        SubView {
+            view,
+            top: rows.start,
+            left: cols.start,
+            rows: rows.len(),
+            cols: cols.len(),
+            row_step: 1,
+            col_step: 1,
+        }
+    }
+
+    /// Only keeps every `row_step`th row and `col_step`th column of the sub-view, starting with the
+    /// first one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either step is 0.
+    pub fn strided(self, row_step: usize, col_step: usize) -> SubView<'a, V> {
+        assert!(row_step > 0 && col_step > 0, "steps must be positive");
+        // the steps stack up if it was already strided
+        SubView {
+            rows: self.rows.div_ceil(row_step),
+            cols: self.cols.div_ceil(col_step),
+            row_step: self.row_step * row_step,
+            col_step: self.col_step * col_step,
+            ..self
+        }
+    }
+}
+
+impl<'a, V: MatrixView + ?Sized> MatrixView for SubView<'a, V> {
+    type Item = V::Item;
+
+    fn dims(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    fn get(&self, row: usize, col: usize) -> V::Item {
+        assert!(
+            row < self.rows && col < self.cols,
+            "({}, {}) is outside of a {}x{} sub-view",
+            row,
+            col,
+            self.rows,
+            self.cols
+        );
+        self.view.get(
+            self.top + row * self.row_step,
+            self.left + col * self.col_step,
+        )
+    }
+
+    fn check_shape(&self) -> Result<(), GridError> {
+        self.view.check_shape()
+    }
+}
+
+/// A matrix with its rows and columns swapped: element (row, col) is element (col, row) of the
+/// matrix underneath.
+///
+/// # Example
+///
+/// ```
+/// use week1::traversal::spiral::spiral;
+/// use week1::view::MatrixView;
+///
+/// let matrix = vec![vec![1, 2, 3], vec![4, 5, 6]];
+/// let transposed = matrix.transposed();
+/// assert_eq!(spiral(&transposed), vec![1, 4, 5, 6, 3, 2]);
+/// ```
+pub struct Transposed<'a, V: ?Sized> {
+    view: &'a V,
+}
+
+impl<'a, V: MatrixView + ?Sized> MatrixView for Transposed<'a, V> {
+    type Item = V::Item;
+
+    fn dims(&self) -> (usize, usize) {
+        let (rows, cols) = self.view.dims();
+        (cols, rows)
+    }
+
+    fn get(&self, row: usize, col: usize) -> V::Item {
+        self.view.get(col, row)
+    }
+
+    fn check_shape(&self) -> Result<(), GridError> {
+        self.view.check_shape()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    fn collect<V: MatrixView>(view: &V) -> Vec<Vec<V::Item>> {
+        let (rows, cols) = view.dims();
+        (0..rows)
+            .map(|row| (0..cols).map(|col| view.get(row, col)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_row_major_view() {
+        let grid = Grid::from_iter(2, 3, ["a", "b", "c", "d", "e", "f"].map(String::from)).unwrap();
+        assert_eq!(MatrixView::dims(&grid), (2, 3));
+        assert_eq!(grid.get(1, 1), Some(&String::from("e")));
+        assert_eq!(MatrixView::get(&grid, 1, 1), "e");
+    }
+
+    #[test]
+    fn test_fold_row() {
+        let push = |mut acc: Vec<_>, x| {
+            acc.push(x);
+            acc
+        };
+        // stored rows are copied out as slices, computed ones go through get, and both agree
+        let matrix = vec![vec![1, 2, 3, 4], vec![5, 6, 7, 8]];
+        let computed = from_fn(2, 4, |row, col| row * 4 + col + 1);
+        assert_eq!(matrix.fold_row(1, 1..3, vec![], push), vec![6, 7]);
+        assert_eq!(computed.fold_row(1, 1..3, vec![], push), vec![6, 7]);
+        assert_eq!(matrix.rfold_row(0, 0..4, vec![], push), vec![4, 3, 2, 1]);
+        assert_eq!(computed.rfold_row(0, 0..4, vec![], push), vec![4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_from_fn() {
+        let matrix = from_fn(2, 3, |row, col| row * 10 + col);
+        assert_eq!(collect(&matrix), vec![vec![0, 1, 2], vec![10, 11, 12]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "(2, 0) is outside of a 2x3 matrix")]
+    fn test_from_fn_outside() {
+        from_fn(2, 3, |row, col| row + col).get(2, 0);
+    }
+
+    #[test]
+    fn test_sub_view() {
+        let matrix = from_fn(6, 7, |row, col| row * 7 + col);
+        let view = matrix.sub_view(1..6, 2..7).strided(2, 3);
+        assert_eq!(
+            collect(&view),
+            vec![vec![9, 12], vec![23, 26], vec![37, 40]]
+        );
+
+        // striding a strided view strides the original even further
+        let sparser = view.strided(2, 1);
+        assert_eq!(collect(&sparser), vec![vec![9, 12], vec![37, 40]]);
+
+        // and a sub-view of a sub-view is relative to the one it was taken from
+        let inner = matrix.sub_view(1..5, 1..5);
+        let inner = inner.sub_view(1..3, 2..4);
+        assert_eq!(collect(&inner), vec![vec![17, 18], vec![24, 25]]);
+    }
+
+    #[test]
+    fn test_sub_view_empty() {
+        let matrix = vec![vec![1, 2], vec![3, 4]];
+        assert_eq!(matrix.sub_view(1..1, 0..2).dims(), (0, 2));
+    }
+
+    #[test]
+    #[should_panic(expected = "columns 1..4 are outside of a matrix with 2 columns")]
+    fn test_sub_view_too_big() {
+        let matrix = vec![vec![1, 2], vec![3, 4]];
+        matrix.sub_view(0..2, 1..4);
+    }
+
+    #[test]
+    fn test_transposed() {
+        let matrix = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        let transposed = matrix.transposed();
+        assert_eq!(
+            collect(&transposed),
+            vec![vec![1, 4], vec![2, 5], vec![3, 6]]
+        );
+        assert_eq!(collect(&transposed.transposed()), matrix);
+    }
+
+    #[test]
+    fn test_check_shape() {
+        let ragged = vec![vec![1, 2, 3], vec![4, 5]];
+        let error = GridError::Ragged {
+            row: 1,
+            expected: 3,
+            actual: 2,
+        };
+        assert_eq!(MatrixView::check_shape(&ragged), Err(error));
+        assert_eq!(ragged.transposed().check_shape(), Err(error));
+        assert_eq!(ragged.sub_view(0..1, 0..3).check_shape(), Err(error));
+        assert_eq!(from_fn(2, 2, |row, col| row + col).check_shape(), Ok(()));
+    }
+}
@@ -1,65 +1,1430 @@
+use crate::grid::{RowMajor, RowMajorMut};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashSet, LinkedList, VecDeque};
+use std::fmt;
+use std::iter::FusedIterator;
+use std::mem;
+use std::num::NonZeroUsize;
+use std::thread;
+
 /// Sorts an array into a "wave" array: nums[0] >= nums[1] <= nums[2] >= nums[3] <= nums[4] >= ...
 /// This is done in-place. The time complexity is O(n), where n is the length of the list.
 ///
 /// This is one of many possible answers, as there may be multiple possible wave arrays.
 /// e.g. [2, 2, 2, 3, 3, 3] is a valid wave array, as is [3, 2, 3, 2, 3, 2], etc.
 ///
-/// # Arguments
+/// NaN isn't greater or less than anything, so a float slice with NaNs in it won't come out as a
+/// wave. Use [`wave_sort_total`] for those.
+///
+/// # Arguments
+///
+/// * `nums` - A mutable slice of numbers, the list to be "sorted"
+///
+/// # Returns
+///
+/// The sorted list
+///
+/// # Example
+///
+/// ```
+/// use week1::wave_sort::wave_sort;
+///
+/// let mut nums = [10, 5, 6, 3, 2, 20, 100, 80];
+/// wave_sort(&mut nums);
+/// assert_eq!(nums, [10, 5, 6, 2, 20, 3, 100, 80]);
+///
+/// let mut nums = [2, 2, 2, 3, 3, 3];
+/// wave_sort(&mut nums);
+/// assert_eq!(nums, [2, 2, 3, 2, 3, 3]);
+/// ```
+///
+/// # Approach
+///
+/// We iterate over the list, swapping elements if they are not in the correct order, i.e. if the
+/// index is even, the element should be greater than the next element, and if the index is odd,
+/// the element should be less than the next element.
+///
+/// # Time and Space Complexity
+///
+/// The time complexity is O(n), since we iterate over the list exactly once. The space complexity 
+/// is O(1) since we "sort" the list in-place and don't allocate any additional memory.
+pub fn wave_sort<T: PartialOrd>(nums: &mut [T]) -> &mut [T] {
+    if nums.is_empty() {
+        return nums;
+    }
+    for i in 0..nums.len() - 1 {
+        if (i % 2 == 0 && nums[i] < nums[i + 1]) || (i % 2 == 1 && nums[i] > nums[i + 1]) {
+            nums.swap(i, i + 1);
+        }
+    }
+    nums
+}
+
+/// The same as [`wave_sort`], but split over as many threads as the machine has cores. See
+/// [`par_wave_sort_with_threads`].
+///
+/// # Example
+///
+/// ```
+/// use week1::wave_sort::{is_wave, par_wave_sort, WaveKind};
+///
+/// let mut nums = (0..1_000_000).collect::<Vec<_>>();
+/// par_wave_sort(&mut nums);
+/// assert!(is_wave(&nums, WaveKind::PeakFirst));
+/// ```
+pub fn par_wave_sort<T: PartialOrd + Send>(nums: &mut [T]) -> &mut [T] {
+    let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    par_wave_sort_with_threads(nums, threads)
+}
+
+/// Sorts an array into a wave array like [`wave_sort`] does, with the work split over up to
+/// `threads` threads.
+///
+/// # Arguments
+///
+/// * `nums` - A mutable slice of numbers, the list to be "sorted"
+/// * `threads` - The most threads to use. Short slices may use fewer, since every thread gets at
+///   least two elements
+///
+/// # Returns
+///
+/// The sorted list. It's a wave, but not always the same one [`wave_sort`] makes, since each chunk
+/// starts from its own first element.
+///
+/// # Example
+///
+/// ```
+/// use week1::wave_sort::par_wave_sort_with_threads;
+///
+/// let mut nums = [7, 6, 5, 4, 3, 2, 1, 0];
+/// par_wave_sort_with_threads(&mut nums, 2);
+/// // [7, 5, 6, 4] and [3, 1, 2, 0], then 4 and 3 swapped where they meet
+/// assert_eq!(nums, [7, 5, 6, 3, 4, 1, 2, 0]);
+/// ```
+///
+/// # Approach
+///
+/// Split the slice into chunks of even length with `chunks_mut`, so every chunk starts at an even
+/// index and has the same pattern of peaks and valleys as the whole slice, and wave sort each one
+/// on its own thread. Then the only pairs that can be the wrong way around are where the chunks
+/// meet, a valley at the end of one chunk and a peak at the start of the next. Swapping those only
+/// makes the valley smaller and the peak bigger, so it can't break anything on either side, and
+/// each boundary is fixed with at most one swap.
+///
+/// # Time and Space Complexity
+///
+/// O(n / threads + threads) time, plus the cost of starting the threads, and O(1) space besides
+/// the threads.
+///
+/// # Panics
+///
+/// Panics if `threads` is 0.
+pub fn par_wave_sort_with_threads<T: PartialOrd + Send>(
+    nums: &mut [T],
+    threads: usize,
+) -> &mut [T] {
+    assert!(threads > 0, "need at least one thread");
+    let chunk = nums.len().div_ceil(threads).next_multiple_of(2).max(2);
+
+    thread::scope(|scope| {
+        for part in nums.chunks_mut(chunk) {
+            scope.spawn(move || wave_sort(part));
+        }
+    });
+
+    // each boundary is between an odd index, which should be a valley, and the peak after it
+    for start in (chunk..nums.len()).step_by(chunk) {
+        if nums[start - 1] > nums[start] {
+            nums.swap(start - 1, start);
+        }
+    }
+    nums
+}
+
+/// The same as [`wave_sort`], but compares the elements with `cmp`, so they don't need to be
+/// PartialOrd, and whatever isn't compared moves along with what is.
+///
+/// # Arguments
+///
+/// * `nums` - A mutable slice of anything, the list to be "sorted"
+/// * `cmp` - Compares two elements, like for `slice::sort_by`
+///
+/// # Returns
+///
+/// The sorted list
+///
+/// # Example
+///
+/// ```
+/// use week1::wave_sort::wave_sort_by;
+///
+/// let mut words = ["a", "bbbb", "cc", "ddd"];
+/// wave_sort_by(&mut words, |a, b| a.len().cmp(&b.len()));
+/// assert_eq!(words, ["bbbb", "a", "ddd", "cc"]);
+/// ```
+///
+/// # Time and Space Complexity
+///
+/// Same as [`wave_sort`]: O(n) time, calling `cmp` n - 1 times, and O(1) space.
+pub fn wave_sort_by<T, F>(nums: &mut [T], mut cmp: F) -> &mut [T]
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    if nums.is_empty() {
+        return nums;
+    }
+    for i in 0..nums.len() - 1 {
+        let order = cmp(&nums[i], &nums[i + 1]);
+        if (i % 2 == 0 && order == Ordering::Less) || (i % 2 == 1 && order == Ordering::Greater) {
+            nums.swap(i, i + 1);
+        }
+    }
+    nums
+}
+
+/// The same as [`wave_sort`], but compares the elements by a key pulled out of each of them, like
+/// a price field of a record. The rest of the record moves with its key.
+///
+/// # Arguments
+///
+/// * `nums` - A mutable slice of anything, the list to be "sorted"
+/// * `key` - Pulls the key to compare out of an element, like for `slice::sort_by_key`
+///
+/// # Returns
+///
+/// The sorted list
+///
+/// # Example
+///
+/// ```
+/// use week1::wave_sort::wave_sort_by_key;
+///
+/// let mut orders = [("apples", 3), ("pears", 5), ("plums", 1)];
+/// wave_sort_by_key(&mut orders, |&(_, price)| price);
+/// assert_eq!(orders, [("pears", 5), ("plums", 1), ("apples", 3)]);
+/// ```
+///
+/// # Time and Space Complexity
+///
+/// O(n) time, calling `key` twice per adjacent pair, and O(1) space.
+pub fn wave_sort_by_key<T, K, F>(nums: &mut [T], mut key: F) -> &mut [T]
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    wave_sort_by(nums, |a, b| key(a).cmp(&key(b)))
+}
+
+/// Floats, which can be put in a total order with `total_cmp` even though they're only PartialOrd.
+pub trait TotalCmp {
+    /// Compares with `total_cmp`, which puts -NaN first and NaN last, and -0.0 before 0.0.
+    fn total_cmp(&self, other: &Self) -> Ordering;
+}
+
+impl TotalCmp for f32 {
+    fn total_cmp(&self, other: &f32) -> Ordering {
+        f32::total_cmp(self, other)
+    }
+}
+
+impl TotalCmp for f64 {
+    fn total_cmp(&self, other: &f64) -> Ordering {
+        f64::total_cmp(self, other)
+    }
+}
+
+/// The same as [`wave_sort`] for floats, but compares them with `total_cmp`, so slices with NaN in
+/// them still come out as a wave. NaN counts as bigger than everything else.
+///
+/// # Example
+///
+/// ```
+/// use week1::wave_sort::wave_sort_total;
+///
+/// let mut nums = [1.0, f64::NAN, 2.0, 3.0];
+/// wave_sort_total(&mut nums);
+/// assert!(nums[0].is_nan());
+/// assert_eq!(nums[1..], [1.0, 3.0, 2.0]);
+/// ```
+///
+/// # Time and Space Complexity
+///
+/// Same as [`wave_sort`]: O(n) time and O(1) space.
+pub fn wave_sort_total<T: TotalCmp>(nums: &mut [T]) -> &mut [T] {
+    wave_sort_by(nums, T::total_cmp)
+}
+
+/// Two neighbouring elements of a [`WaveSequence`], which can be looked at and swapped.
+#[derive(Debug)]
+pub struct AdjacentPair<'a, T> {
+    index: usize,
+    first: &'a mut T,
+    second: &'a mut T,
+}
+
+impl<'a, T> AdjacentPair<'a, T> {
+    /// The index of the first element of the pair; the second one is at index + 1.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn first(&self) -> &T {
+        self.first
+    }
+
+    pub fn second(&self) -> &T {
+        self.second
+    }
+
+    /// Swaps the two elements in the sequence.
+    pub fn swap(&mut self) {
+        mem::swap(self.first, self.second);
+    }
+}
+
+/// A sequence that can be walked front to back one pair of neighbours at a time, like the windows
+/// of a slice, but with each pair swappable. That's all [`wave_sort_sequence`] needs, so it works
+/// on linked lists too, which can't be indexed cheaply.
+pub trait WaveSequence {
+    type Item;
+
+    /// Calls `f` on every pair of neighbours, (0, 1), (1, 2), ..., in order. Swapping a pair
+    /// changes what the next pair starts with.
+    fn for_each_pair<F>(&mut self, f: F)
+    where
+        F: FnMut(AdjacentPair<'_, Self::Item>);
+}
+
+/// Walks the pairs of neighbours of anything with an `iter_mut`, keeping hold of the previous
+/// element as we go.
+fn for_each_pair_in<'a, T: 'a, I, F>(iter: I, mut f: F)
+where
+    I: IntoIterator<Item = &'a mut T>,
+    F: FnMut(AdjacentPair<'_, T>),
+{
+    let mut iter = iter.into_iter();
+    let Some(mut first) = iter.next() else {
+        return;
+    };
+    for (index, second) in iter.enumerate() {
+        f(AdjacentPair {
+            index,
+            first: &mut *first,
+            second: &mut *second,
+        });
+        first = second;
+    }
+}
+
+impl<T> WaveSequence for [T] {
+    type Item = T;
+
+    fn for_each_pair<F>(&mut self, f: F)
+    where
+        F: FnMut(AdjacentPair<'_, T>),
+    {
+        for_each_pair_in(self.iter_mut(), f);
+    }
+}
+
+impl<T> WaveSequence for VecDeque<T> {
+    type Item = T;
+
+    fn for_each_pair<F>(&mut self, f: F)
+    where
+        F: FnMut(AdjacentPair<'_, T>),
+    {
+        for_each_pair_in(self.iter_mut(), f);
+    }
+}
+
+impl<T> WaveSequence for LinkedList<T> {
+    type Item = T;
+
+    fn for_each_pair<F>(&mut self, f: F)
+    where
+        F: FnMut(AdjacentPair<'_, T>),
+    {
+        for_each_pair_in(self.iter_mut(), f);
+    }
+}
+
+/// The same as [`wave_sort`], but for any [`WaveSequence`]: slices, `VecDeque`s and
+/// `LinkedList`s. It's one pass over the sequence, in place, without collecting it first.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::{LinkedList, VecDeque};
+///
+/// use week1::wave_sort::wave_sort_sequence;
+///
+/// let mut list = LinkedList::from([10, 5, 6, 3, 2, 20, 100, 80]);
+/// wave_sort_sequence(&mut list);
+/// assert!(list.into_iter().eq([10, 5, 6, 2, 20, 3, 100, 80]));
+///
+/// let mut deque = VecDeque::from([1, 2, 3, 4]);
+/// wave_sort_sequence(&mut deque);
+/// assert_eq!(deque, [2, 1, 4, 3]);
+/// ```
+///
+/// # Time and Space Complexity
+///
+/// Same as [`wave_sort`]: O(n) time and O(1) space.
+pub fn wave_sort_sequence<S>(seq: &mut S) -> &mut S
+where
+    S: WaveSequence + ?Sized,
+    S::Item: PartialOrd,
+{
+    seq.for_each_pair(|mut pair| {
+        let (i, a, b) = (pair.index(), pair.first(), pair.second());
+        if (i % 2 == 0 && a < b) || (i % 2 == 1 && a > b) {
+            pair.swap();
+        }
+    });
+    seq
+}
+
+/// Adds [`wave`](WaveExt::wave) to every iterator.
+pub trait WaveExt: Iterator + Sized {
+    /// Puts the items in wave order as they go past, the way [`wave_sort`] would put them if they
+    /// were collected into a slice, without collecting them. It only ever holds on to one item, so
+    /// it works on streams that never end.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use week1::wave_sort::WaveExt;
+    ///
+    /// let wave = (1..).wave().take(6).collect::<Vec<_>>();
+    /// assert_eq!(wave, [2, 1, 4, 3, 6, 5]);
+    /// ```
+    ///
+    /// # Approach
+    ///
+    /// [`wave_sort`] only ever swaps the element it's looking at with the next one, and never looks
+    /// back, so once it's moved on from a position, that position is done. We keep the element
+    /// that would be at the current position, and when the next one comes in, whichever of the
+    /// two belongs at the current position gets emitted and the other one is kept for the next.
+    ///
+    /// # Time and Space Complexity
+    ///
+    /// O(1) time per item and O(1) space.
+    fn wave(self) -> Wave<Self>
+    where
+        Self::Item: PartialOrd,
+    {
+        Wave {
+            iter: self,
+            held: None,
+            started: false,
+            peak: true,
+        }
+    }
+}
+
+impl<I: Iterator> WaveExt for I {}
+
+/// An iterator that emits the items of another in wave order. See [`WaveExt::wave`].
+#[derive(Debug, Clone)]
+pub struct Wave<I: Iterator> {
+    iter: I,
+    /// The item that'll be emitted next, unless the next one from `iter` has to go first.
+    held: Option<I::Item>,
+    started: bool,
+    /// Whether the next item emitted should be a peak, rather than counting the items, so this
+    /// can go on forever.
+    peak: bool,
+}
+
+impl<I> Iterator for Wave<I>
+where
+    I: Iterator,
+    I::Item: PartialOrd,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if !self.started {
+            self.started = true;
+            self.held = self.iter.next();
+        }
+        let held = self.held.take()?;
+        let Some(next) = self.iter.next() else {
+            return Some(held);
+        };
+        let in_order = if self.peak {
+            held >= next
+        } else {
+            held <= next
+        };
+        self.peak = !self.peak;
+        if in_order {
+            self.held = Some(next);
+            Some(held)
+        } else {
+            self.held = Some(held);
+            Some(next)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if !self.started {
+            return self.iter.size_hint();
+        }
+        let held = usize::from(self.held.is_some());
+        if held == 0 {
+            return (0, Some(0));
+        }
+        let (lower, upper) = self.iter.size_hint();
+        (
+            lower.saturating_add(held),
+            upper.and_then(|upper| upper.checked_add(held)),
+        )
+    }
+}
+
+impl<I> ExactSizeIterator for Wave<I>
+where
+    I: ExactSizeIterator,
+    I::Item: PartialOrd,
+{
+}
+
+// once `held` runs out we never call `iter` again
+impl<I> FusedIterator for Wave<I>
+where
+    I: Iterator,
+    I::Item: PartialOrd,
+{
+}
+
+/// The ways [`wave_sort_with`] can pick which of the many possible waves to make.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WaveStrategy {
+    /// One pass of swapping neighbours that are the wrong way around, like [`wave_sort`].
+    #[default]
+    Greedy,
+    /// The lexicographically smallest wave. See [`wave_sort_smallest`].
+    Smallest,
+    /// The wave that takes the fewest swaps of neighbours to get to, so every element moves at
+    /// most one place. See [`wave_sort_min_adjacent_swaps`].
+    MinimalAdjacentSwaps,
+}
+
+/// Sorts an array into a wave array, picking which one with `strategy`. Every strategy makes a
+/// wave that starts with a peak, like [`wave_sort`].
+///
+/// # Example
+///
+/// ```
+/// use week1::wave_sort::{wave_sort_with, WaveStrategy};
+///
+/// let mut nums = [3, 1, 2, 4];
+/// wave_sort_with(&mut nums, WaveStrategy::Smallest);
+/// assert_eq!(nums, [2, 1, 4, 3]);
+///
+/// let mut nums = [3, 1, 2, 4];
+/// wave_sort_with(&mut nums, WaveStrategy::MinimalAdjacentSwaps);
+/// assert_eq!(nums, [3, 1, 4, 2]);
+/// ```
+pub fn wave_sort_with<T: Ord>(nums: &mut [T], strategy: WaveStrategy) -> &mut [T] {
+    match strategy {
+        WaveStrategy::Greedy => wave_sort(nums),
+        WaveStrategy::Smallest => wave_sort_smallest(nums),
+        WaveStrategy::MinimalAdjacentSwaps => {
+            wave_sort_min_adjacent_swaps(nums);
+            nums
+        }
+    }
+}
+
+/// Sorts an array into the lexicographically smallest wave array, so the same values always make
+/// the same wave, whatever order they came in.
+///
+/// # Example
+///
+/// ```
+/// use week1::wave_sort::wave_sort_smallest;
+///
+/// let mut nums = [10, 5, 6, 3, 2, 20, 100, 80];
+/// wave_sort_smallest(&mut nums);
+/// assert_eq!(nums, [3, 2, 6, 5, 20, 10, 100, 80]);
+/// ```
+///
+/// # Approach
+///
+/// Sort the list, then swap every pair (0, 1), (2, 3), ... The first element has to be bigger than
+/// something, so it can't be any smaller than the second smallest value, and then the smallest
+/// value is the only thing that can go next to it. The same goes for every pair after that, out
+/// of what's left: each valley is smaller than the peak after it, which is bigger than everything
+/// before it.
+///
+/// # Time and Space Complexity
+///
+/// O(n log n) time for the sort, and O(1) space.
+pub fn wave_sort_smallest<T: Ord>(nums: &mut [T]) -> &mut [T] {
+    nums.sort();
+    for pair in nums.chunks_exact_mut(2) {
+        pair.swap(0, 1);
+    }
+    nums
+}
+
+/// Sorts an array into a wave array with as few swaps of neighbours as possible, and says which
+/// swaps it made, so the result differs from the input as little as it can. The swaps never
+/// overlap, so every element moves at most one place.
+///
+/// # Arguments
+///
+/// * `nums` - A mutable slice of numbers, the list to be "sorted"
+///
+/// # Returns
+///
+/// The swaps that were made, in order, as pairs of indices (i, i + 1). None of them overlap, so
+/// they could have been made in any order.
+///
+/// # Example
+///
+/// ```
+/// use week1::wave_sort::wave_sort_min_adjacent_swaps;
+///
+/// let mut nums = [3, 1, 2, 4];
+/// assert_eq!(wave_sort_min_adjacent_swaps(&mut nums), vec![(2, 3)]);
+/// assert_eq!(nums, [3, 1, 4, 2]);
+/// ```
+///
+/// # Approach
+///
+/// Dynamic programming from left to right. Each position ends up holding the element that was
+/// there or one of its neighbours, and whether a pair is the right way around only depends on what
+/// ends up at those two positions. So for each position we keep the fewest swaps that make
+/// everything up to it a wave, for each of the three elements it could hold, then walk back from
+/// the cheapest way to finish.
+///
+/// # Time and Space Complexity
+///
+/// O(n) time, and O(n) space for the table of how we got to each position.
+pub fn wave_sort_min_adjacent_swaps<T: Ord>(nums: &mut [T]) -> Vec<(usize, usize)> {
+    let len = nums.len();
+    if len < 2 {
+        return Vec::new();
+    }
+
+    // best[i][from] is the fewest swaps that make nums[..=i] a wave, with the element that was at
+    // i - 1 + from ending up at i, and the `from` of position i - 1 that it came from
+    let mut best = vec![[None; 3]; len];
+    best[0][1] = Some((0, 0));
+    best[0][2] = Some((1, 0));
+    for i in 0..len - 1 {
+        for from in 0..3 {
+            let Some((swaps, _)) = best[i][from] else {
+                continue;
+            };
+            // if i took the element from i + 1, then i + 1 has to take the one from i
+            let nexts: &[usize] = match from {
+                2 => &[0],
+                _ if i + 2 < len => &[1, 2],
+                _ => &[1],
+            };
+            for &next in nexts {
+                let (a, b) = (&nums[i + from - 1], &nums[i + next]);
+                let in_order = if i % 2 == 0 { a >= b } else { a <= b };
+                let swaps = swaps + usize::from(next == 2);
+                if in_order && best[i + 1][next].is_none_or(|(fewest, _)| swaps < fewest) {
+                    best[i + 1][next] = Some((swaps, from));
+                }
+            }
+        }
+    }
+
+    let last = (0..2)
+        .filter_map(|from| best[len - 1][from].map(|(swaps, _)| (swaps, from)))
+        .min();
+    let (_, mut from) = last.expect("there's always a wave with settled last position");
+    let mut swaps = Vec::new();
+    for i in (0..len).rev() {
+        if from == 2 {
+            swaps.push((i, i + 1));
+        }
+        from = best[i][from].unwrap().1;
+    }
+    swaps.reverse();
+    for &(a, b) in &swaps {
+        nums.swap(a, b);
+    }
+    swaps
+}
+
+/// Sorts an array into a strict wave array: nums[0] > nums[1] < nums[2] > nums[3] < ... Unlike
+/// [`wave_sort`], equal neighbours aren't allowed, so this is only possible if no value is repeated
+/// too many times.
+///
+/// # Arguments
+///
+/// * `nums` - A mutable slice of numbers, the list to be "sorted"
+///
+/// # Returns
+///
+/// The sorted list, or an error if there are too many copies of some value for any arrangement of
+/// them to be a strict wave. In that case the slice has still been rearranged.
+///
+/// # Example
+///
+/// ```
+/// use week1::wave_sort::{is_strict_wave, strict_wave_sort, StrictWaveError, WaveKind};
+///
+/// let mut nums = [1, 5, 1, 1, 6, 4];
+/// strict_wave_sort(&mut nums).unwrap();
+/// assert!(is_strict_wave(&nums, WaveKind::PeakFirst));
+///
+/// assert_eq!(
+///     strict_wave_sort(&mut [3, 3, 3, 3, 1, 2]),
+///     Err(StrictWaveError { count: 4, len: 6 })
+/// );
+/// ```
+///
+/// # Approach
+///
+/// Find the median, then put everything smaller than it in the valleys (the odd indices) from the
+/// left, everything bigger in the peaks (the even indices) from the right, and the copies of the
+/// median in between. That way the copies of the median fill the right end of the valleys and the
+/// left end of the peaks, as far apart as they can be, and every other neighbour is a small value
+/// next to a big one.
+///
+/// Instead of partitioning and then interleaving, we partition through "virtual indices": virtual
+/// index i is real index (1 + 2 * i) % (n | 1), which runs over the odd indices and then the even
+/// ones. A three-way (Dutch national flag) partition with small values first over the virtual
+/// indices puts everything in place in one pass.
+///
+/// If the median is so common that two copies of it still end up next to each other, there's no
+/// strict wave at all, which we find out by checking the result.
+///
+/// # Time and Space Complexity
+///
+/// The median is found with `select_nth_unstable`, which is O(n) worst case, and the partition and
+/// the check are one pass each, so it's O(n) time. The space complexity is O(1).
+pub fn strict_wave_sort<T: Ord + Clone>(nums: &mut [T]) -> Result<&mut [T], StrictWaveError> {
+    let len = nums.len();
+    if len < 2 {
+        return Ok(nums);
+    }
+    let median = nums.select_nth_unstable((len - 1) / 2).1.clone();
+
+    let virtual_index = |i: usize| (1 + 2 * i) % (len | 1);
+    let (mut smaller, mut i, mut bigger) = (0, 0, len);
+    while i < bigger {
+        match nums[virtual_index(i)].cmp(&median) {
+            Ordering::Less => {
+                nums.swap(virtual_index(smaller), virtual_index(i));
+                smaller += 1;
+                i += 1;
+            }
+            Ordering::Greater => {
+                bigger -= 1;
+                nums.swap(virtual_index(i), virtual_index(bigger));
+            }
+            Ordering::Equal => i += 1,
+        }
+    }
+
+    if is_strict_wave(nums, WaveKind::PeakFirst) {
+        Ok(nums)
+    } else {
+        Err(StrictWaveError {
+            count: bigger - smaller,
+            len,
+        })
+    }
+}
+
+/// Why [`strict_wave_sort`] couldn't make a strict wave: the median of the `len` elements is there
+/// `count` times, and that many copies can't all be kept apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StrictWaveError {
+    pub count: usize,
+    pub len: usize,
+}
+
+impl fmt::Display for StrictWaveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "a value appears {} times out of {}, too many for a strict wave",
+            self.count, self.len
+        )
+    }
+}
+
+impl std::error::Error for StrictWaveError {}
+
+/// Which way a wave starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WaveKind {
+    /// nums[0] >= nums[1] <= nums[2] >= ..., which is what [`wave_sort`] makes.
+    #[default]
+    PeakFirst,
+    /// nums[0] <= nums[1] >= nums[2] <= ...
+    ValleyFirst,
+}
+
+impl WaveKind {
+    /// Whether nums[index] should be a peak, i.e. >= its neighbours.
+    pub fn is_peak(self, index: usize) -> bool {
+        (index % 2 == 1) == (self == WaveKind::ValleyFirst)
+    }
+}
+
+/// The first place a slice stops being a wave: nums[index] and nums[index + 1] are the wrong way
+/// around.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WaveViolation<'a, T> {
+    /// The index of the first element of the pair.
+    pub index: usize,
+    /// The pair itself, (nums[index], nums[index + 1]).
+    pub pair: (&'a T, &'a T),
+    /// Whether nums[index] should have been a peak, or else a valley.
+    pub peak: bool,
+}
+
+impl<'a, T: fmt::Display> fmt::Display for WaveViolation<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (a, b) = self.pair;
+        let relation = if self.peak { ">=" } else { "<=" };
+        write!(
+            f,
+            "expected nums[{}] {} nums[{}], but {} and {} aren't",
+            self.index,
+            relation,
+            self.index + 1,
+            a,
+            b
+        )
+    }
+}
+
+/// Checks whether a slice is a wave of the given kind. Empty and single element slices are waves
+/// of either kind.
+///
+/// # Example
+///
+/// ```
+/// use week1::wave_sort::{is_wave, WaveKind};
+///
+/// assert!(is_wave(&[3, 2, 3, 2, 3], WaveKind::PeakFirst));
+/// assert!(is_wave(&[3, 4, 3, 4], WaveKind::ValleyFirst));
+/// assert!(!is_wave(&[1, 2, 3], WaveKind::PeakFirst));
+/// ```
+///
+/// # Time and Space Complexity
+///
+/// O(n) time and O(1) space.
+pub fn is_wave<T: PartialOrd>(nums: &[T], kind: WaveKind) -> bool {
+    find_wave_violation(nums, kind).is_none()
+}
+
+/// Checks whether a slice is a strict wave of the given kind, where neighbours can't be equal.
+///
+/// # Example
+///
+/// ```
+/// use week1::wave_sort::{is_strict_wave, WaveKind};
+///
+/// assert!(is_strict_wave(&[3, 2, 3, 2, 3], WaveKind::PeakFirst));
+/// assert!(!is_strict_wave(&[3, 3, 3], WaveKind::PeakFirst));
+/// ```
+///
+/// # Time and Space Complexity
+///
+/// O(n) time and O(1) space.
+pub fn is_strict_wave<T: PartialOrd>(nums: &[T], kind: WaveKind) -> bool {
+    nums.windows(2).enumerate().all(|(index, pair)| {
+        if kind.is_peak(index) {
+            pair[0] > pair[1]
+        } else {
+            pair[0] < pair[1]
+        }
+    })
+}
+
+/// Finds the first adjacent pair of a slice that breaks the wave pattern, if there is one.
+///
+/// # Arguments
+///
+/// * `nums` - The list to check
+/// * `kind` - Whether it should start with a peak or a valley
+///
+/// # Returns
+///
+/// Where the slice first goes wrong, or None if it's a wave
+///
+/// # Example
+///
+/// ```
+/// use week1::wave_sort::{find_wave_violation, WaveKind};
+///
+/// let nums = [5, 1, 4, 6, 2];
+/// let violation = find_wave_violation(&nums, WaveKind::PeakFirst).unwrap();
+/// assert_eq!(violation.index, 2);
+/// assert_eq!(violation.pair, (&4, &6));
+/// assert_eq!(violation.to_string(), "expected nums[2] >= nums[3], but 4 and 6 aren't");
+/// ```
+///
+/// # Time and Space Complexity
+///
+/// O(n) time and O(1) space, and it stops at the first violation.
+pub fn find_wave_violation<T: PartialOrd>(
+    nums: &[T],
+    kind: WaveKind,
+) -> Option<WaveViolation<'_, T>> {
+    nums.windows(2).enumerate().find_map(|(index, pair)| {
+        let peak = kind.is_peak(index);
+        (!in_wave_order(&pair[0], &pair[1], peak)).then_some(WaveViolation {
+            index,
+            pair: (&pair[0], &pair[1]),
+            peak,
+        })
+    })
+}
+
+/// Counts the adjacent pairs of a slice that are the wrong way around for a wave of the given kind,
+/// as a measure of how far from a wave it is. It's 0 exactly when the slice is a wave.
+///
+/// # Example
+///
+/// ```
+/// use week1::wave_sort::{count_wave_violations, WaveKind};
+///
+/// // sorted is as far from a wave as it gets: every other pair is backwards
+/// assert_eq!(count_wave_violations(&[1, 2, 3, 4, 5, 6], WaveKind::PeakFirst), 3);
+/// assert_eq!(count_wave_violations(&[1, 2, 3, 4, 5, 6], WaveKind::ValleyFirst), 2);
+/// ```
+///
+/// # Time and Space Complexity
+///
+/// O(n) time and O(1) space.
+pub fn count_wave_violations<T: PartialOrd>(nums: &[T], kind: WaveKind) -> usize {
+    nums.windows(2)
+        .enumerate()
+        .filter(|(index, pair)| !in_wave_order(&pair[0], &pair[1], kind.is_peak(*index)))
+        .count()
+}
+
+/// Whether a is on the right side of b, if a should be a peak or a valley. Anything that doesn't
+/// compare, like NaN, is never in order.
+fn in_wave_order<T: PartialOrd>(a: &T, b: &T, peak: bool) -> bool {
+    if peak {
+        a >= b
+    } else {
+        a <= b
+    }
+}
+
+/// Sorts a matrix into a 2D wave, like a checkerboard: the cells where `kind` says
+/// [`is_peak`](WaveKind::is_peak)(row + col) are greater than or equal to all four of their
+/// neighbours, and the rest are less than or equal to theirs. Every row and every column comes out
+/// as a 1D wave.
+///
+/// # Arguments
+///
+/// * `matrix` - A matrix of anything Ord, e.g. a Vec<Vec<T>> or a [`Grid`](crate::grid::Grid)
+/// * `kind` - Whether the top left cell is a peak or a valley
+///
+/// # Example
+///
+/// ```
+/// use week1::wave_sort::{is_wave_2d, wave_sort_2d, WaveKind};
+///
+/// let mut matrix = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+/// wave_sort_2d(&mut matrix, WaveKind::PeakFirst);
+/// assert!(is_wave_2d(&matrix, WaveKind::PeakFirst));
+/// // the five peaks are the five biggest numbers
+/// assert!(matrix[0][0] >= 5 && matrix[1][1] >= 5 && matrix[2][2] >= 5);
+/// assert!(matrix[0][1] <= 4 && matrix[1][0] <= 4);
+/// ```
+///
+/// # Approach
+///
+/// Neighbours in a checkerboard are always different colours, so it's enough for every peak to
+/// be at least as big as every valley. Line up the cells with the valleys first and the peaks
+/// after them, select the value that goes at the first peak, like a quickselect, so that
+/// everything smaller than it comes before it, and then move the values to the cells they lined
+/// up with.
+///
+/// # Time and Space Complexity
+///
+/// O(n) time on average, where n is the number of cells, and O(n) space for the order of the
+/// cells. The values themselves are only ever swapped, never cloned.
+pub fn wave_sort_2d<M>(matrix: &mut M, kind: WaveKind) -> &mut M
+where
+    M: RowMajorMut + ?Sized,
+    M::Item: Ord,
+{
+    let (rows, cols) = matrix.dims();
+    let (mut cells, peaks): (Vec<_>, Vec<_>) = (0..rows)
+        .flat_map(|row| (0..cols).map(move |col| (row, col)))
+        .partition(|&(row, col)| !kind.is_peak(row + col));
+    let valleys = cells.len();
+    cells.extend(peaks);
+    if valleys == 0 || valleys == cells.len() {
+        return matrix;
+    }
+
+    // order[i] is the index in `cells` of the value that should end up in cells[i]
+    let value = |i: usize| &matrix.row(cells[i].0)[cells[i].1];
+    let mut order = (0..cells.len()).collect::<Vec<_>>();
+    order.select_nth_unstable_by(valleys, |&a, &b| value(a).cmp(value(b)));
+
+    // follow each cycle of the permutation, swapping each value into place
+    for start in 0..order.len() {
+        let mut i = start;
+        while order[i] != start {
+            let from = order[i];
+            matrix.swap(cells[i], cells[from]);
+            order[i] = i;
+            i = from;
+        }
+        order[i] = i;
+    }
+    matrix
+}
+
+/// Checks whether a matrix is a 2D wave of the given kind, as [`wave_sort_2d`] makes them.
+///
+/// # Example
+///
+/// ```
+/// use week1::wave_sort::{is_wave_2d, WaveKind};
+///
+/// assert!(is_wave_2d(&vec![vec![3, 1], vec![2, 4]], WaveKind::PeakFirst));
+/// assert!(!is_wave_2d(&vec![vec![3, 1], vec![4, 2]], WaveKind::PeakFirst));
+/// assert!(is_wave_2d(&vec![vec![1, 3], vec![2, 1]], WaveKind::ValleyFirst));
+/// ```
+///
+/// # Time and Space Complexity
+///
+/// O(n) time, where n is the number of cells, since each pair of neighbours is compared once, and
+/// O(1) space.
+pub fn is_wave_2d<M>(matrix: &M, kind: WaveKind) -> bool
+where
+    M: RowMajor + ?Sized,
+    M::Item: PartialOrd,
+{
+    let (rows, cols) = matrix.dims();
+    (0..rows).all(|row| {
+        let current = matrix.row(row);
+        let below = (row + 1 < rows).then(|| matrix.row(row + 1));
+        (0..cols).all(|col| {
+            let peak = kind.is_peak(row + col);
+            let right = current
+                .get(col + 1)
+                .is_none_or(|right| in_wave_order(&current[col], right, peak));
+            let down = below.is_none_or(|below| in_wave_order(&current[col], &below[col], peak));
+            right && down
+        })
+    })
+}
+
+/// Which way a sequence goes from one element to the next. Equal neighbours go neither way, so a
+/// value can't sit next to a copy of itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// nums[i] < nums[i + 1]
+    Up,
+    /// nums[i] > nums[i + 1]
+    Down,
+}
+
+/// The reasons [`arrange_by_pattern`] or [`parse_pattern`] can fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternError {
+    /// n elements have n - 1 steps between them, so the pattern needs `expected` directions.
+    WrongLength { expected: usize, actual: usize },
+    /// The character at `index` of a pattern string isn't a U or a D.
+    InvalidDirection { index: usize, found: char },
+    /// Some values are repeated so often that no arrangement of them fits the pattern.
+    Impossible,
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PatternError::WrongLength { expected, actual } => write!(
+                f,
+                "expected a pattern of {} directions, got {}",
+                expected, actual
+            ),
+            PatternError::InvalidDirection { index, found } => write!(
+                f,
+                "expected U or D at index {} of the pattern, found {:?}",
+                index, found
+            ),
+            PatternError::Impossible => write!(f, "no arrangement of the values fits the pattern"),
+        }
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+/// Reads a pattern like "UUD" or "dudu", one U (up) or D (down) per step.
+///
+/// # Example
+///
+/// ```
+/// use week1::wave_sort::{parse_pattern, Direction};
+///
+/// assert_eq!(parse_pattern("UD"), Ok(vec![Direction::Up, Direction::Down]));
+/// assert!(parse_pattern("UXD").is_err());
+/// ```
+pub fn parse_pattern(pattern: &str) -> Result<Vec<Direction>, PatternError> {
+    pattern
+        .chars()
+        .enumerate()
+        .map(|(index, found)| match found {
+            'U' | 'u' => Ok(Direction::Up),
+            'D' | 'd' => Ok(Direction::Down),
+            _ => Err(PatternError::InvalidDirection { index, found }),
+        })
+        .collect()
+}
+
+/// Checks whether every step of a slice goes the way the pattern says.
+///
+/// # Example
+///
+/// ```
+/// use week1::wave_sort::{fits_pattern, parse_pattern};
+///
+/// let pattern = parse_pattern("UUD").unwrap();
+/// assert!(fits_pattern(&[1, 2, 3, 0], &pattern));
+/// assert!(!fits_pattern(&[1, 2, 0, 3], &pattern));
+/// ```
+pub fn fits_pattern<T: PartialOrd>(nums: &[T], pattern: &[Direction]) -> bool {
+    pattern.len() == nums.len().saturating_sub(1)
+        && nums
+            .windows(2)
+            .zip(pattern)
+            .all(|(pair, direction)| match direction {
+                Direction::Up => pair[0] < pair[1],
+                Direction::Down => pair[0] > pair[1],
+            })
+}
+
+/// Rearranges a slice so that it goes up and down the way a pattern says. [`strict_wave_sort`] is
+/// the special case where the pattern is "DUDU...".
+///
+/// # Arguments
+///
+/// * `nums` - A mutable slice of numbers, the list to be "sorted"
+/// * `pattern` - Which way to go at each step, one direction for each pair of neighbours
+///
+/// # Returns
+///
+/// The rearranged list, or an error if the pattern isn't one shorter than the list, or if values
+/// are repeated so often that no arrangement fits it, like [1, 1, 1] and "UD". In that case the
+/// slice has still been sorted. If the elements are all different, there's always a way.
+///
+/// # Example
+///
+/// ```
+/// use week1::wave_sort::{arrange_by_pattern, parse_pattern, PatternError};
+///
+/// let mut nums = [1, 2, 3, 4, 5];
+/// arrange_by_pattern(&mut nums, &parse_pattern("UUDD").unwrap()).unwrap();
+/// assert_eq!(nums, [1, 2, 5, 4, 3]);
+///
+/// assert_eq!(
+///     arrange_by_pattern(&mut nums, &parse_pattern("UD").unwrap()),
+///     Err(PatternError::WrongLength { expected: 4, actual: 2 })
+/// );
+///
+/// let mut nums = [1, 2, 1];
+/// arrange_by_pattern(&mut nums, &parse_pattern("UD").unwrap()).unwrap();
+/// assert_eq!(nums, [1, 2, 1]);
+/// let mut nums = [1, 1, 1];
+/// assert_eq!(
+///     arrange_by_pattern(&mut nums, &parse_pattern("UD").unwrap()),
+///     Err(PatternError::Impossible)
+/// );
+/// ```
+///
+/// # Approach
 ///
-/// * `nums` - A mutable slice of numbers, the list to be "sorted"
+/// Sort the list. If the elements are all different, fill in the result from left to right: a step
+/// up takes the smallest value that's left, and a step down takes the biggest, so whatever comes
+/// next is on the right side of it, and the last element takes the one value left over. With
+/// repeats, hand out the values from the smallest up instead, each copy to a position whose smaller
+/// neighbours are filled already, and back up to try other positions when a value runs out of
+/// places. Either way that says where every element of the sorted list goes, and following the
+/// cycles of that permutation moves them all there with swaps.
 ///
-/// # Returns
+/// # Time and Space Complexity
 ///
-/// The sorted list
+/// Sorting is O(n log n), and with all different elements the rest is O(n). Repeated values need
+/// the search, which is O(n log n) per value when the first positions it tries work out, but can
+/// take exponential time when they don't. For a "DUDU..." pattern [`strict_wave_sort`] always
+/// takes O(n). The space complexity is O(n) for the permutation, plus whatever the search
+/// remembers.
+pub fn arrange_by_pattern<'a, T: Ord>(
+    nums: &'a mut [T],
+    pattern: &[Direction],
+) -> Result<&'a mut [T], PatternError> {
+    let expected = nums.len().saturating_sub(1);
+    if pattern.len() != expected {
+        return Err(PatternError::WrongLength {
+            expected,
+            actual: pattern.len(),
+        });
+    }
+    if nums.is_empty() {
+        return Ok(nums);
+    }
+    nums.sort();
+    let counts = nums
+        .chunk_by(|a, b| a == b)
+        .map(<[T]>::len)
+        .collect::<Vec<_>>();
+
+    // dest[i] is where the i-th smallest element goes
+    let mut dest = vec![0; nums.len()];
+    if counts.len() == nums.len() {
+        let (mut low, mut high) = (0, nums.len() - 1);
+        for (position, direction) in pattern.iter().enumerate() {
+            match direction {
+                Direction::Up => {
+                    dest[low] = position;
+                    low += 1;
+                }
+                Direction::Down => {
+                    dest[high] = position;
+                    high -= 1;
+                }
+            }
+        }
+        dest[low] = pattern.len();
+    } else {
+        let levels = pattern_levels(pattern, &counts).ok_or(PatternError::Impossible)?;
+        // where the copies of each value that haven't been sent anywhere yet start
+        let mut next = counts
+            .iter()
+            .scan(0, |start, &count| {
+                *start += count;
+                Some(*start - count)
+            })
+            .collect::<Vec<_>>();
+        for (position, level) in levels.into_iter().enumerate() {
+            dest[next[level]] = position;
+            next[level] += 1;
+        }
+    }
+
+    for start in 0..nums.len() {
+        // keep sending whatever is at `start` to where it belongs, until the right one comes back
+        while dest[start] != start {
+            let target = dest[start];
+            nums.swap(start, target);
+            dest.swap(start, target);
+        }
+    }
+    Ok(nums)
+}
+
+/// Finds which value each position of a pattern gets, when there are `counts[level]` copies of the
+/// level-th smallest value, or None if there's no way to fit them all in. The positions with the
+/// longest climb still above them are tried first, and since the free positions say exactly which
+/// ones are filled, the sets of them that lead nowhere are remembered and never tried twice.
+fn pattern_levels(pattern: &[Direction], counts: &[usize]) -> Option<Vec<usize>> {
+    let len = pattern.len() + 1;
+    let goes = |i: usize, direction: Direction| pattern.get(i) == Some(&direction);
+    // the neighbours that need a smaller value than position i, and the ones that need a bigger one
+    let below = |i: usize| {
+        let left = (i > 0 && goes(i - 1, Direction::Up)).then(|| i - 1);
+        left.into_iter()
+            .chain(goes(i, Direction::Down).then_some(i + 1))
+    };
+    let above = |i: usize| {
+        let left = (i > 0 && goes(i - 1, Direction::Down)).then(|| i - 1);
+        left.into_iter()
+            .chain(goes(i, Direction::Up).then_some(i + 1))
+    };
+
+    // a position with d steps down in a row next to it needs d smaller values, and one with u steps
+    // up needs u bigger ones, so handing each value out to the positions that have to be lowest,
+    // among the ones that can be that low, has to work out. That catches runs too long for the
+    // values up front, which the search could take a very long time to run out of options on.
+    let climb = steps_in_a_row(pattern, Direction::Up);
+    let drop = steps_in_a_row(pattern, Direction::Down);
+    let mut by_drop = (0..len).collect::<Vec<_>>();
+    by_drop.sort_by_key(|&i| drop[i]);
+    let mut by_drop = by_drop.into_iter().peekable();
+    let mut waiting = BinaryHeap::new();
+    for (level, &count) in counts.iter().enumerate() {
+        while let Some(i) = by_drop.next_if(|&i| drop[i] <= level) {
+            waiting.push(Reverse(counts.len().checked_sub(climb[i] + 1)?));
+        }
+        for _ in 0..count {
+            let Reverse(highest) = waiting.pop()?;
+            if highest < level {
+                return None;
+            }
+        }
+    }
+
+    let mut level = vec![None; len];
+    // for each value so far, the positions that were free for it and which of them it took
+    let mut stack: Vec<(Vec<usize>, Vec<usize>)> = Vec::new();
+    let mut dead_ends = HashSet::new();
+    let mut free = (0..len)
+        .filter(|&i| below(i).next().is_none())
+        .collect::<Vec<_>>();
+    while stack.len() < counts.len() {
+        let count = counts[stack.len()];
+        free.sort_by_key(|&i| (Reverse(climb[i]), i));
+        if free.len() >= count && !dead_ends.contains(&free) {
+            stack.push((free, (0..count).collect()));
+        } else {
+            dead_ends.insert(free);
+            // move on to the next choice of positions for the latest value that has one left
+            loop {
+                let (free, picks) = stack.last_mut()?;
+                for &pick in picks.iter() {
+                    level[free[pick]] = None;
+                }
+                if next_combination(picks, free.len()) {
+                    break;
+                }
+                if let Some((free, _)) = stack.pop() {
+                    dead_ends.insert(free);
+                }
+            }
+        }
+
+        let depth = stack.len() - 1;
+        let (taken_from, picks) = &stack[depth];
+        for &pick in picks {
+            level[taken_from[pick]] = Some(depth);
+        }
+        free = taken_from
+            .iter()
+            .copied()
+            .filter(|&i| level[i].is_none())
+            .chain(picks.iter().flat_map(|&pick| above(taken_from[pick])))
+            .filter(|&i| below(i).all(|j| level[j].is_some()))
+            .collect();
+        free.sort_unstable();
+        free.dedup();
+    }
+    level.into_iter().collect()
+}
+
+/// For each position of a pattern, the most steps in a row that go `direction` away from it, to
+/// either side.
+fn steps_in_a_row(pattern: &[Direction], direction: Direction) -> Vec<usize> {
+    let mut steps = vec![0; pattern.len() + 1];
+    for (i, &step) in pattern.iter().enumerate().rev() {
+        if step == direction {
+            steps[i] = steps[i + 1] + 1;
+        }
+    }
+    // going left is going the other way
+    let mut run = 0;
+    for (i, &step) in pattern.iter().enumerate() {
+        run = if step == direction { 0 } else { run + 1 };
+        steps[i + 1] = steps[i + 1].max(run);
+    }
+    steps
+}
+
+/// Steps `picks`, a sorted choice of indices below `len`, to the next one in lexicographic order,
+/// or returns false if this was the last one.
+fn next_combination(picks: &mut [usize], len: usize) -> bool {
+    let count = picks.len();
+    let Some(i) = (0..count).rfind(|&i| picks[i] < len - count + i) else {
+        return false;
+    };
+    picks[i] += 1;
+    for j in i + 1..count {
+        picks[j] = picks[j - 1] + 1;
+    }
+    true
+}
+
+/// Counts how many orderings of pattern.len() + 1 different values fit a pattern, or None if
+/// there are more than fit in a u128.
 ///
 /// # Example
 ///
 /// ```
-/// let mut nums = [10, 5, 6, 3, 2, 20, 100, 80];
-/// wave_sort(&mut nums);
-/// assert_eq!(nums, [10, 5, 6, 2, 20, 3, 100, 80]);
+/// use week1::wave_sort::{count_pattern_permutations, parse_pattern};
 ///
-/// let mut nums = [2, 2, 2, 3, 3, 3];
-/// wave_sort(&mut nums);
-/// assert_eq!(nums, [2, 2, 2, 3, 3, 3]);
+/// // 1 3 2 and 2 3 1
+/// assert_eq!(count_pattern_permutations(&parse_pattern("UD").unwrap()), Some(2));
+/// // the alternating ones are counted by the Euler zigzag numbers
+/// assert_eq!(count_pattern_permutations(&parse_pattern("DUDUDU").unwrap()), Some(272));
 /// ```
 ///
 /// # Approach
 ///
-/// We iterate over the list, swapping elements if they are not in the correct order, i.e. if the
-/// index is even, the element should be greater than the next element, and if the index is odd,
-/// the element should be less than the next element.
+/// Build the orderings up one element at a time, keeping track of how many ways there are for the
+/// latest element to be the j-th smallest so far. Only the relative order matters, so a new element
+/// that ranks j-th pushes the ones at j and above up by one. Stepping up, it has to rank above the
+/// previous element, so the ways for it to be j-th are all the ways for the previous one to be
+/// below j, and stepping down it's all the ways for the previous one to be at j or above. Both are
+/// running sums over the previous counts.
 ///
 /// # Time and Space Complexity
 ///
-/// The time complexity is O(n), since we iterate over the list exactly once. The space complexity 
-/// is O(1) since we "sort" the list in-place and don't allocate any additional memory.
-pub fn wave_sort<T: PartialOrd + Copy>(nums: &mut [T]) -> &mut [T] {
-    if nums.is_empty() {
-        return nums;
-    }
-    for i in 0..nums.len() - 1 {
-        if (i % 2 == 0 && nums[i] < nums[i + 1]) || (i % 2 == 1 && nums[i] > nums[i + 1]) {
-            nums.swap(i, i + 1);
+/// O(n^2) time and O(n) space, where n is the length of the pattern.
+pub fn count_pattern_permutations(pattern: &[Direction]) -> Option<u128> {
+    let mut ways: Vec<u128> = vec![1];
+    for direction in pattern {
+        let mut next = Vec::with_capacity(ways.len() + 1);
+        match direction {
+            Direction::Up => {
+                let mut below: u128 = 0;
+                next.push(0);
+                for &count in &ways {
+                    below = below.checked_add(count)?;
+                    next.push(below);
+                }
+            }
+            Direction::Down => {
+                let mut above: u128 = 0;
+                next.push(0);
+                for &count in ways.iter().rev() {
+                    above = above.checked_add(count)?;
+                    next.push(above);
+                }
+                next.reverse();
+            }
         }
+        ways = next;
     }
-    nums
+    ways.into_iter().try_fold(0u128, u128::checked_add)
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::grid::Grid;
 
     fn check_valid_wave(nums: &[i32]) {
+        check_valid_wave_by(nums, i32::cmp);
+    }
+
+    fn check_valid_wave_by<T, F>(nums: &[T], mut cmp: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
         if nums.is_empty() || nums.len() == 1 {
             return;
         }
-        let case = nums[0] >= nums[1];
+        let case = cmp(&nums[0], &nums[1]) != Ordering::Less;
         for i in 1..nums.len() - 1 {
             if i % 2 == if case { 0 } else { 1 } {
-                assert!(nums[i] >= nums[i + 1]);
+                assert!(cmp(&nums[i], &nums[i + 1]) != Ordering::Less);
             } else {
-                assert!(nums[i] <= nums[i + 1]);
+                assert!(cmp(&nums[i], &nums[i + 1]) != Ordering::Greater);
             }
         }
     }
@@ -130,4 +1495,730 @@ mod test {
         wave_sort(&mut nums);
         assert!(nums.is_empty());
     }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Record {
+        price: u32,
+        name: String,
+    }
+
+    fn records(prices: &[u32]) -> Vec<Record> {
+        prices
+            .iter()
+            .map(|&price| Record {
+                price,
+                name: format!("item {}", price),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_wave_sort_by_key() {
+        let mut items = records(&[10, 5, 6, 3, 2, 20, 100, 80]);
+        wave_sort_by_key(&mut items, |item| item.price);
+        check_valid_wave_by(&items, |a, b| a.price.cmp(&b.price));
+        // the names moved with the prices
+        for item in &items {
+            assert_eq!(item.name, format!("item {}", item.price));
+        }
+
+        let mut plain = [10, 5, 6, 3, 2, 20, 100, 80];
+        wave_sort(&mut plain);
+        let prices = items.iter().map(|item| item.price).collect::<Vec<_>>();
+        assert_eq!(prices, plain);
+    }
+
+    #[test]
+    fn test_wave_sort_by() {
+        // a backwards comparison makes the wave start with a valley
+        let mut items = records(&[1, 2, 3, 4, 5, 6]);
+        wave_sort_by(&mut items, |a, b| b.price.cmp(&a.price));
+        let prices = items.iter().map(|item| item.price).collect::<Vec<_>>();
+        assert_eq!(prices, [1, 3, 2, 5, 4, 6]);
+
+        let mut empty: Vec<Record> = Vec::new();
+        wave_sort_by(&mut empty, |a, b| a.price.cmp(&b.price));
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_wave_sort_total() {
+        let mut nums = [f64::NAN, 1.0, 2.0, f64::NAN, -0.0, 0.0, -f64::NAN];
+        wave_sort_total(&mut nums);
+        check_valid_wave_by(&nums, f64::total_cmp);
+
+        let mut nums = [3.0f32, f32::NAN, 1.0, 2.0];
+        wave_sort_total(&mut nums);
+        check_valid_wave_by(&nums, f32::total_cmp);
+    }
+
+    #[test]
+    fn test_wave_sort_nan_is_not_a_wave() {
+        // plain wave_sort can't see NaN, which is why wave_sort_total exists
+        let mut nums = [1.0, f64::NAN, 2.0, 3.0];
+        wave_sort(&mut nums);
+        assert!(nums[0].partial_cmp(&nums[1]).is_none());
+    }
+
+    #[test]
+    fn test_is_wave() {
+        assert!(is_wave(&[3, 2, 3, 2, 3, 2, 3], WaveKind::PeakFirst));
+        assert!(is_wave(&[3, 4, 3, 4, 3, 4, 3], WaveKind::ValleyFirst));
+        assert!(!is_wave(&[3, 4, 3, 4, 3, 4, 3], WaveKind::PeakFirst));
+        // equal neighbours are both peaks and valleys
+        assert!(is_wave(&[3; 8], WaveKind::PeakFirst));
+        assert!(is_wave(&[3; 8], WaveKind::ValleyFirst));
+        assert!(is_wave::<i32>(&[], WaveKind::PeakFirst));
+        assert!(is_wave(&[1], WaveKind::ValleyFirst));
+        assert!(!is_wave(&[1.0, f64::NAN], WaveKind::PeakFirst));
+    }
+
+    #[test]
+    fn test_is_wave_after_wave_sort() {
+        let mut nums = [10, 5, 6, 3, 2, 20, 100, 80];
+        assert!(!is_wave(&nums, WaveKind::PeakFirst));
+        wave_sort(&mut nums);
+        assert!(is_wave(&nums, WaveKind::PeakFirst));
+        check_valid_wave(&nums);
+    }
+
+    #[test]
+    fn test_find_wave_violation() {
+        let nums = [1, 2, 3, 4, 5, 6];
+        assert_eq!(
+            find_wave_violation(&nums, WaveKind::PeakFirst),
+            Some(WaveViolation {
+                index: 0,
+                pair: (&1, &2),
+                peak: true
+            })
+        );
+        let violation = find_wave_violation(&nums, WaveKind::ValleyFirst).unwrap();
+        assert_eq!((violation.index, violation.pair), (1, (&2, &3)));
+        assert_eq!(
+            violation.to_string(),
+            "expected nums[1] >= nums[2], but 2 and 3 aren't"
+        );
+
+        let valley = find_wave_violation(&[5, 1, 4, 2, 1], WaveKind::PeakFirst).unwrap();
+        assert_eq!(valley.index, 3);
+        assert!(!valley.peak);
+        assert!(find_wave_violation(&[5, 1, 4, 2, 3], WaveKind::PeakFirst).is_none());
+    }
+
+    #[test]
+    fn test_count_wave_violations() {
+        let wave = [3, 2, 3, 2];
+        assert_eq!(count_wave_violations(&wave, WaveKind::PeakFirst), 0);
+        assert_eq!(count_wave_violations(&wave, WaveKind::ValleyFirst), 3);
+        let descending = [6, 5, 4, 3, 2, 1];
+        assert_eq!(count_wave_violations(&descending, WaveKind::PeakFirst), 2);
+        assert_eq!(count_wave_violations::<i32>(&[], WaveKind::PeakFirst), 0);
+    }
+
+    /// Steps to the next lexicographic permutation, or returns false if this was the last one.
+    fn next_permutation(nums: &mut [i32]) -> bool {
+        let Some(i) = (1..nums.len()).rfind(|&i| nums[i - 1] < nums[i]) else {
+            return false;
+        };
+        let j = (i..nums.len()).rfind(|&j| nums[j] > nums[i - 1]).unwrap();
+        nums.swap(i - 1, j);
+        nums[i..].reverse();
+        true
+    }
+
+    /// Whether any arrangement of `nums` is a strict wave, by trying all of them.
+    fn strict_wave_exists(nums: &[i32]) -> bool {
+        let mut nums = nums.to_vec();
+        nums.sort();
+        loop {
+            if is_strict_wave(&nums, WaveKind::PeakFirst) {
+                return true;
+            }
+            if !next_permutation(&mut nums) {
+                return false;
+            }
+        }
+    }
+
+    #[test]
+    fn test_strict_wave_sort() {
+        let mut nums = [1, 5, 1, 1, 6, 4];
+        strict_wave_sort(&mut nums).unwrap();
+        assert!(is_strict_wave(&nums, WaveKind::PeakFirst));
+        check_valid_wave(&nums);
+
+        let mut nums = [1, 3, 2, 2, 3, 1];
+        strict_wave_sort(&mut nums).unwrap();
+        assert!(is_strict_wave(&nums, WaveKind::PeakFirst));
+
+        let mut empty: [i32; 0] = [];
+        assert!(strict_wave_sort(&mut empty).is_ok());
+        assert_eq!(strict_wave_sort(&mut [7]), Ok(&mut [7][..]));
+    }
+
+    #[test]
+    fn test_strict_wave_sort_duplicates() {
+        // half of them the same is fine, as long as they can take every other spot
+        let mut nums = [5, 5, 5, 5, 1, 2, 3, 9];
+        strict_wave_sort(&mut nums).unwrap();
+        assert!(is_strict_wave(&nums, WaveKind::PeakFirst));
+
+        let mut nums = (0..1000).map(|i| i % 2).collect::<Vec<_>>();
+        strict_wave_sort(&mut nums).unwrap();
+        assert!(is_strict_wave(&nums, WaveKind::PeakFirst));
+
+        // but all the same never is, even though it's a fine non-strict wave
+        let mut same = [3; 8];
+        assert_eq!(
+            strict_wave_sort(&mut same),
+            Err(StrictWaveError { count: 8, len: 8 })
+        );
+        let mut nums = [2, 2, 2, 2, 2, 1, 3];
+        let error = strict_wave_sort(&mut nums).unwrap_err();
+        assert_eq!(error, StrictWaveError { count: 5, len: 7 });
+        assert_eq!(
+            error.to_string(),
+            "a value appears 5 times out of 7, too many for a strict wave"
+        );
+        // the smallest value can only go in the valleys, and there's one fewer of those
+        assert!(strict_wave_sort(&mut [1, 1, 2]).is_err());
+        // and the same goes for the middle value here
+        assert!(strict_wave_sort(&mut [1, 2, 2, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn test_strict_wave_sort_brute_force() {
+        // every multiset of up to 7 values from 0..4, in sorted order so there's one of each
+        let mut multisets = vec![vec![]];
+        let mut start = 0;
+        for _ in 0..7 {
+            let end = multisets.len();
+            for m in start..end {
+                let last = multisets[m].last().copied().unwrap_or(0);
+                for value in last..4 {
+                    let mut next = multisets[m].clone();
+                    next.push(value);
+                    multisets.push(next);
+                }
+            }
+            start = end;
+        }
+
+        for nums in multisets {
+            let possible = strict_wave_exists(&nums);
+            let mut sorted = nums.clone();
+            let result = strict_wave_sort(&mut sorted);
+            assert_eq!(result.is_ok(), possible, "{:?}", nums);
+            if possible {
+                assert!(is_strict_wave(&sorted, WaveKind::PeakFirst), "{:?}", nums);
+            }
+            // whatever happened, it's still the same values
+            sorted.sort();
+            assert_eq!(sorted, nums);
+        }
+    }
+
+    /// Every pattern for a list of len elements.
+    fn all_patterns(len: usize) -> Vec<Vec<Direction>> {
+        let steps = len.saturating_sub(1);
+        (0..1 << steps)
+            .map(|bits: u32| {
+                (0..steps)
+                    .map(|i| match bits >> i & 1 {
+                        0 => Direction::Up,
+                        _ => Direction::Down,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_arrange_by_pattern_wave() {
+        // a "DUDU..." pattern is a strict wave
+        let mut nums = [10, 5, 6, 3, 2, 20, 100, 80];
+        let pattern = parse_pattern("DUDUDUD").unwrap();
+        arrange_by_pattern(&mut nums, &pattern).unwrap();
+        check_valid_wave(&nums);
+        assert!(is_strict_wave(&nums, WaveKind::PeakFirst));
+
+        // and it can be done exactly when strict_wave_sort can
+        for nums in [
+            vec![1, 1, 2, 2, 3],
+            vec![1, 1, 1, 2, 3],
+            vec![5, 5, 5, 1, 1, 1],
+        ] {
+            let pattern = parse_pattern(&"DU".repeat(nums.len())[..nums.len() - 1]).unwrap();
+            let mut arranged = nums.clone();
+            let mut strict = nums.clone();
+            assert_eq!(
+                arrange_by_pattern(&mut arranged, &pattern).is_ok(),
+                strict_wave_sort(&mut strict).is_ok(),
+                "{:?}",
+                nums
+            );
+        }
+    }
+
+    /// Whether any arrangement of `nums` fits `pattern`, by trying all of them.
+    fn pattern_arrangement_exists(nums: &[i32], pattern: &[Direction]) -> bool {
+        let mut nums = nums.to_vec();
+        nums.sort();
+        loop {
+            if fits_pattern(&nums, pattern) {
+                return true;
+            }
+            if !next_permutation(&mut nums) {
+                return false;
+            }
+        }
+    }
+
+    #[test]
+    fn test_arrange_by_pattern_all_patterns() {
+        for len in 0..=8 {
+            for pattern in all_patterns(len) {
+                // all different values fit strictly
+                let mut nums = (0..len as i32).rev().collect::<Vec<_>>();
+                arrange_by_pattern(&mut nums, &pattern).unwrap();
+                assert!(fits_pattern(&nums, &pattern), "{:?}", pattern);
+            }
+        }
+    }
+
+    #[test]
+    fn test_arrange_by_pattern_repeats() {
+        // check against trying every ordering, for every list of up to 7 elements that are each 0,
+        // 1, 2 or 3, and every pattern
+        for len in 0..=7 {
+            for index in 0..4usize.pow(len) {
+                let original = (0..len)
+                    .map(|i| (index / 4usize.pow(i) % 4) as i32)
+                    .collect::<Vec<_>>();
+                // the order they come in doesn't matter, so only try each set of values once
+                if !original.is_sorted() {
+                    continue;
+                }
+                for pattern in all_patterns(len as usize) {
+                    let mut nums = original.clone();
+                    let result = arrange_by_pattern(&mut nums, &pattern).map(|nums| nums.to_vec());
+                    if pattern_arrangement_exists(&original, &pattern) {
+                        let arranged = result.unwrap();
+                        assert!(fits_pattern(&arranged, &pattern), "{:?}", arranged);
+                        nums.sort();
+                        assert_eq!(nums, original);
+                    } else {
+                        assert_eq!(result, Err(PatternError::Impossible), "{:?}", original);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_arrange_by_pattern_repeats_big() {
+        // 1000 values with 10 copies of each, which only fit a pattern with long enough climbs
+        let values = (0..1000).map(|i| i % 100).collect::<Vec<i32>>();
+        for pattern in ["DU", "UUDD", "UUUUUUUUUD", "UDDUDD"] {
+            let pattern = parse_pattern(&pattern.repeat(1000)[..999]).unwrap();
+            let mut nums = values.clone();
+            arrange_by_pattern(&mut nums, &pattern).unwrap();
+            assert!(fits_pattern(&nums, &pattern));
+        }
+        // 100 steps up in a row need 101 different values
+        let pattern = parse_pattern(&("U".repeat(100) + &"DU".repeat(500))[..999]).unwrap();
+        let mut nums = values.clone();
+        assert_eq!(
+            arrange_by_pattern(&mut nums, &pattern),
+            Err(PatternError::Impossible)
+        );
+    }
+
+    #[test]
+    fn test_arrange_by_pattern_moves_everything() {
+        let mut words = ["pear", "fig", "apple", "kiwi", "banana"].map(String::from);
+        arrange_by_pattern(&mut words, &parse_pattern("UUDD").unwrap()).unwrap();
+        assert_eq!(words, ["apple", "banana", "pear", "kiwi", "fig"]);
+    }
+
+    #[test]
+    fn test_arrange_by_pattern_errors() {
+        let mut nums = [1, 2, 3];
+        let error = arrange_by_pattern(&mut nums, &[Direction::Up]).unwrap_err();
+        assert_eq!(
+            error,
+            PatternError::WrongLength {
+                expected: 2,
+                actual: 1
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "expected a pattern of 2 directions, got 1"
+        );
+        // equal neighbours go neither way, so a peak needs something smaller on both sides
+        let mut flat = [1, 1, 1];
+        let error = arrange_by_pattern(&mut flat, &parse_pattern("UD").unwrap()).unwrap_err();
+        assert_eq!(error, PatternError::Impossible);
+        assert_eq!(
+            error.to_string(),
+            "no arrangement of the values fits the pattern"
+        );
+        assert!(!fits_pattern(&flat, &parse_pattern("UD").unwrap()));
+
+        // an empty list has no steps, so it only fits an empty pattern
+        let mut empty: [i32; 0] = [];
+        assert!(arrange_by_pattern(&mut empty, &[]).is_ok());
+        assert!(arrange_by_pattern(&mut empty, &[Direction::Down]).is_err());
+
+        assert_eq!(
+            parse_pattern("udU?"),
+            Err(PatternError::InvalidDirection {
+                index: 3,
+                found: '?'
+            })
+        );
+    }
+
+    #[test]
+    fn test_count_pattern_permutations() {
+        // check against trying every ordering of 0..n
+        for len in 1..=7 {
+            for pattern in all_patterns(len) {
+                let mut nums = (0..len as i32).collect::<Vec<_>>();
+                let mut expected = 0;
+                loop {
+                    if fits_pattern(&nums, &pattern) {
+                        expected += 1;
+                    }
+                    if !next_permutation(&mut nums) {
+                        break;
+                    }
+                }
+                assert_eq!(
+                    count_pattern_permutations(&pattern),
+                    Some(expected),
+                    "{:?}",
+                    pattern
+                );
+            }
+        }
+        assert_eq!(count_pattern_permutations(&[]), Some(1));
+        assert_eq!(count_pattern_permutations(&[Direction::Down; 100]), Some(1));
+        // the number of alternating orderings of 61 values is way past u128
+        let zigzag = parse_pattern(&"DU".repeat(30)).unwrap();
+        assert_eq!(count_pattern_permutations(&zigzag), None);
+    }
+
+    #[test]
+    fn test_wave_sort_smallest() {
+        // check against trying every ordering
+        for len in 0..=7 {
+            for seed in 0..20 {
+                let mut nums = (0..len).map(|i| (i * 7 + seed) % 4).collect::<Vec<i32>>();
+                let mut smallest = nums.clone();
+                wave_sort_smallest(&mut smallest);
+                check_valid_wave(&smallest);
+
+                nums.sort();
+                let mut expected = None;
+                loop {
+                    if is_wave(&nums, WaveKind::PeakFirst) {
+                        expected = Some(nums.clone());
+                        break;
+                    }
+                    if !next_permutation(&mut nums) {
+                        break;
+                    }
+                }
+                assert_eq!(Some(smallest), expected);
+            }
+        }
+    }
+
+    /// The fewest swaps of neighbours that turn `nums` into a wave, by breadth first search.
+    fn fewest_swaps(nums: &[i32]) -> usize {
+        let mut seen = std::collections::HashSet::from([nums.to_vec()]);
+        let mut queue = std::collections::VecDeque::from([(nums.to_vec(), 0)]);
+        while let Some((nums, swaps)) = queue.pop_front() {
+            if is_wave(&nums, WaveKind::PeakFirst) {
+                return swaps;
+            }
+            for i in 0..nums.len() - 1 {
+                let mut next = nums.clone();
+                next.swap(i, i + 1);
+                if seen.insert(next.clone()) {
+                    queue.push_back((next, swaps + 1));
+                }
+            }
+        }
+        unreachable!("sorting into a wave always works")
+    }
+
+    #[test]
+    fn test_wave_sort_min_adjacent_swaps() {
+        for len in 0..=6 {
+            for index in 0..4usize.pow(len) {
+                let original = (0..len)
+                    .map(|i| (index / 4usize.pow(i) % 4) as i32)
+                    .collect::<Vec<_>>();
+                let mut nums = original.clone();
+                let swaps = wave_sort_min_adjacent_swaps(&mut nums);
+                check_valid_wave(&nums);
+                assert_eq!(swaps.len(), fewest_swaps(&original), "{:?}", original);
+
+                // the swaps don't overlap, and they're what turned the input into the output
+                assert!(swaps.windows(2).all(|pair| pair[0].1 < pair[1].0));
+                let mut replayed = original.clone();
+                for (a, b) in swaps {
+                    assert_eq!(b, a + 1);
+                    replayed.swap(a, b);
+                }
+                assert_eq!(replayed, nums);
+            }
+        }
+    }
+
+    #[test]
+    fn test_wave_sort_min_adjacent_swaps_permutations() {
+        // always finishes, on every ordering of up to 8 distinct values
+        for len in 0..=8 {
+            let mut original = (0..len).collect::<Vec<i32>>();
+            loop {
+                let mut nums = original.clone();
+                let swaps = wave_sort_min_adjacent_swaps(&mut nums);
+                check_valid_wave(&nums);
+                assert!(swaps.len() <= len as usize / 2);
+                if !next_permutation(&mut original) {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_wave_sort_min_adjacent_swaps_big() {
+        // a wave already needs nothing
+        let mut nums = (0..1000).map(|i| i % 2).rev().collect::<Vec<_>>();
+        assert!(wave_sort_min_adjacent_swaps(&mut nums).is_empty());
+
+        // sorted needs every other pair swapped, which is also what the greedy pass does
+        let mut nums = (0..1000).collect::<Vec<_>>();
+        let swaps = wave_sort_min_adjacent_swaps(&mut nums);
+        assert_eq!(swaps.len(), 500);
+        check_valid_wave(&nums);
+
+        // greedy swaps 3 and 2 first, then has to swap 3 and 20 too
+        let mut nums = [10, 5, 6, 3, 2, 20, 100, 80];
+        let mut greedy = nums;
+        assert_eq!(wave_sort_min_adjacent_swaps(&mut nums), vec![(4, 5)]);
+        assert_eq!(nums, [10, 5, 6, 3, 20, 2, 100, 80]);
+        wave_sort(&mut greedy);
+        assert_eq!(greedy, [10, 5, 6, 2, 20, 3, 100, 80]);
+    }
+
+    #[test]
+    fn test_wave_sort_with() {
+        for strategy in [
+            WaveStrategy::Greedy,
+            WaveStrategy::Smallest,
+            WaveStrategy::MinimalAdjacentSwaps,
+        ] {
+            let mut nums = [10, 5, 6, 3, 2, 20, 100, 80];
+            wave_sort_with(&mut nums, strategy);
+            check_valid_wave(&nums);
+            assert!(is_wave(&nums, WaveKind::PeakFirst), "{:?}", strategy);
+        }
+    }
+
+    #[test]
+    fn test_wave_sort_sequence() {
+        for nums in [
+            vec![],
+            vec![1],
+            vec![10, 5, 6, 3, 2, 20, 100, 80],
+            vec![2, 2, 2, 3, 3, 3],
+            (0..11).collect(),
+            (0..11).rev().collect(),
+        ] {
+            let mut expected = nums.clone();
+            wave_sort(&mut expected);
+
+            let mut slice = nums.clone();
+            wave_sort_sequence(&mut slice[..]);
+            assert_eq!(slice, expected);
+
+            let mut deque = VecDeque::from(nums.clone());
+            wave_sort_sequence(&mut deque);
+            assert_eq!(deque, expected);
+
+            let mut list = nums.into_iter().collect::<LinkedList<_>>();
+            wave_sort_sequence(&mut list);
+            assert_eq!(list.into_iter().collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn test_wave_sort_sequence_wrapped_deque() {
+        // a deque that wraps around its buffer has its pairs split across two slices
+        let mut deque = VecDeque::with_capacity(8);
+        deque.extend([0, 0, 0, 0, 0, 0]);
+        deque.drain(..5);
+        deque.extend(1..7);
+        assert_ne!(deque.as_slices().1, []);
+        wave_sort_sequence(&mut deque);
+        check_valid_wave(deque.make_contiguous());
+    }
+
+    #[test]
+    fn test_adjacent_pairs() {
+        let mut list = LinkedList::from([1, 2, 3]);
+        let mut seen = Vec::new();
+        list.for_each_pair(|mut pair| {
+            seen.push((pair.index(), *pair.first(), *pair.second()));
+            pair.swap();
+        });
+        // the first swap carries 1 along to the second pair
+        assert_eq!(seen, [(0, 1, 2), (1, 1, 3)]);
+        assert!(list.into_iter().eq([2, 3, 1]));
+    }
+
+    #[test]
+    fn test_wave_iter() {
+        for nums in [
+            vec![],
+            vec![1],
+            vec![1, 2],
+            vec![10, 5, 6, 3, 2, 20, 100, 80],
+            vec![2, 2, 2, 3, 3, 3],
+            (0..11).collect(),
+            (0..11).rev().collect(),
+        ] {
+            let mut expected = nums.clone();
+            wave_sort(&mut expected);
+
+            let iter = nums.into_iter().wave();
+            assert_eq!(iter.len(), expected.len());
+            assert_eq!(iter.collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn test_wave_iter_infinite() {
+        let wave = (0..).wave().take(1001).collect::<Vec<_>>();
+        check_valid_wave(&wave);
+        let wave = [3, 1, 4, 1, 5, 9, 2, 6]
+            .into_iter()
+            .cycle()
+            .wave()
+            .take(1000)
+            .collect::<Vec<_>>();
+        check_valid_wave(&wave);
+    }
+
+    #[test]
+    fn test_wave_iter_is_lazy() {
+        // only ever one item ahead of what's been emitted
+        let pulled = std::cell::Cell::new(0);
+        let mut wave = (0..).inspect(|_| pulled.set(pulled.get() + 1)).wave();
+        assert_eq!(pulled.get(), 0);
+        for emitted in 1..=10 {
+            wave.next();
+            assert_eq!(pulled.get(), emitted + 1);
+        }
+    }
+
+    #[test]
+    fn test_wave_iter_fused() {
+        let mut wave = [1, 2].into_iter().wave();
+        assert_eq!(wave.size_hint(), (2, Some(2)));
+        assert_eq!(wave.next(), Some(2));
+        assert_eq!(wave.size_hint(), (1, Some(1)));
+        assert_eq!(wave.next(), Some(1));
+        assert_eq!(wave.next(), None);
+        assert_eq!(wave.next(), None);
+        assert_eq!(wave.size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn test_wave_sort_2d() {
+        for rows in 0..=6 {
+            for cols in 0..=6 {
+                for kind in [WaveKind::PeakFirst, WaveKind::ValleyFirst] {
+                    let mut matrix = (0..rows)
+                        .map(|row| (0..cols).map(|col| (row * 5 + col * 3) % 7).collect())
+                        .collect::<Vec<Vec<usize>>>();
+                    let mut before = matrix.concat();
+                    wave_sort_2d(&mut matrix, kind);
+                    assert!(is_wave_2d(&matrix, kind), "{:?}", matrix);
+
+                    // nothing was lost or duplicated along the way
+                    let mut after = matrix.concat();
+                    before.sort();
+                    after.sort();
+                    assert_eq!(before, after);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_wave_sort_2d_rows_are_waves() {
+        let mut grid = Grid::from_flat(5, 6, (0..30).rev().collect()).unwrap();
+        wave_sort_2d(&mut grid, WaveKind::PeakFirst);
+        assert!(is_wave_2d(&grid, WaveKind::PeakFirst));
+        for row in 0..5 {
+            let kind = if row % 2 == 0 {
+                WaveKind::PeakFirst
+            } else {
+                WaveKind::ValleyFirst
+            };
+            assert!(is_wave(grid.row(row), kind));
+        }
+    }
+
+    #[test]
+    fn test_is_wave_2d() {
+        let checkerboard = vec![vec![1, 0, 1], vec![0, 1, 0]];
+        assert!(is_wave_2d(&checkerboard, WaveKind::PeakFirst));
+        assert!(!is_wave_2d(&checkerboard, WaveKind::ValleyFirst));
+
+        // fine along the rows, but not down the first column
+        let rows_only = vec![vec![1, 0, 1], vec![2, 1, 2]];
+        assert!(!is_wave_2d(&rows_only, WaveKind::PeakFirst));
+
+        let empty: Vec<Vec<i32>> = vec![];
+        assert!(is_wave_2d(&empty, WaveKind::PeakFirst));
+        assert!(is_wave_2d(&vec![vec![7]], WaveKind::ValleyFirst));
+    }
+
+    #[test]
+    fn test_par_wave_sort() {
+        for len in 0..40 {
+            for threads in 1..=6 {
+                let mut nums = (0..len).map(|i| (i * 7 + 3) % 11).collect::<Vec<i32>>();
+                let mut before = nums.clone();
+                par_wave_sort_with_threads(&mut nums, threads);
+                check_valid_wave(&nums);
+
+                let mut after = nums.clone();
+                before.sort();
+                after.sort();
+                assert_eq!(before, after, "{len} elements, {threads} threads");
+            }
+        }
+    }
+
+    #[test]
+    fn test_par_wave_sort_repairs_boundaries() {
+        // the two chunks are waves on their own, but 4 and 3 meet the wrong way around
+        let mut nums = [7, 6, 5, 4, 3, 2, 1, 0];
+        par_wave_sort_with_threads(&mut nums, 2);
+        assert_eq!(nums, [7, 5, 6, 3, 4, 1, 2, 0]);
+
+        let mut nums = (0..100_000).rev().collect::<Vec<_>>();
+        par_wave_sort(&mut nums);
+        check_valid_wave(&nums);
+    }
 }
@@ -7,6 +7,7 @@ use crate::prefix_sum::NumMatrix;
 use crate::subarray_sums_equal_k::{subarray_sums, subarray_sums_2ptr};
 use heater_radius::min_heater_radius;
 use max_guests::maximum_guests;
+use week1::grid::Grid;
 
 fn main() {
     let mut arrivals = vec![1, 2, 9, 5, 5];
@@ -31,13 +32,15 @@ fn main() {
     let k = 2;
     println!("subarray sums: {}", subarray_sums_2ptr(&nums, k));
 
-    let matrix = NumMatrix::new(&[
+    let grid = Grid::from_rows(vec![
         vec![3, 0, 1, 4, 2],
         vec![5, 6, 3, 2, 1],
         vec![1, 2, 0, 1, 5],
         vec![4, 1, 0, 1, 7],
         vec![1, 0, 3, 0, 5],
-    ]);
+    ])
+    .expect("every row has 5 columns");
+    let matrix = NumMatrix::new(&grid).expect("the grid isn't empty");
 
     println!("prefix sums: {}", matrix.sum_region(2, 1, 4, 3));
 }
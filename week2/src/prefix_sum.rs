@@ -1,24 +1,40 @@
+use week1::grid::Grid;
+
 pub struct NumMatrix {
     prefix_sums: Vec<Vec<i32>>,
 }
 
+/// Why a [`NumMatrix`] can't be built.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumMatrixError {
+    /// The matrix has no elements, so there's no region to sum.
+    Empty,
+}
+
+impl std::fmt::Display for NumMatrixError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            NumMatrixError::Empty => write!(f, "matrix cannot be empty!"),
+        }
+    }
+}
+
+impl std::error::Error for NumMatrixError {}
+
 impl NumMatrix {
-    pub fn new(matrix: &[Vec<i32>]) -> NumMatrix {
-        NumMatrix {
+    pub fn new(matrix: &Grid<i32>) -> Result<NumMatrix, NumMatrixError> {
+        // the grid has already checked that every row is the same length
+        if matrix.is_empty() {
+            return Err(NumMatrixError::Empty);
+        }
+        Ok(NumMatrix {
             prefix_sums: {
-                let rows = matrix.len();
-                assert_ne!(rows, 0, "matrix cannot be empty!");
-                let cols = matrix[0].len();
+                let (rows, cols) = matrix.dims();
                 let mut prefix_sums = vec![vec![0; cols + 1]; rows + 1];
 
                 for row in 0..rows {
-                    assert_eq!(
-                        cols,
-                        matrix[row].len(),
-                        "every row must have the same length!"
-                    );
                     for col in 0..cols {
-                        prefix_sums[row + 1][col + 1] = matrix[row][col]
+                        prefix_sums[row + 1][col + 1] = matrix[(row, col)]
                             + prefix_sums[row + 1][col]
                             + prefix_sums[row][col + 1]
                             - prefix_sums[row][col];
@@ -26,7 +42,7 @@ impl NumMatrix {
                 }
                 prefix_sums
             },
-        }
+        })
     }
 
     pub fn sum_region(&self, row1: usize, col1: usize, row2: usize, col2: usize) -> i32 {
@@ -44,13 +60,15 @@ mod test {
 
     #[test]
     fn test_num_matrix_1() {
-        let matrix = NumMatrix::new(&[
+        let grid = Grid::from_rows(vec![
             vec![3, 0, 1, 4, 2],
             vec![5, 6, 3, 2, 1],
             vec![1, 2, 0, 1, 5],
             vec![4, 1, 0, 1, 7],
             vec![1, 0, 3, 0, 5],
-        ]);
+        ])
+        .unwrap();
+        let matrix = NumMatrix::new(&grid).unwrap();
 
         assert_eq!(matrix.sum_region(2, 1, 4, 3), 8);
         assert_eq!(matrix.sum_region(1, 1, 2, 2), 11);
@@ -59,7 +77,7 @@ mod test {
 
     #[test]
     fn test_num_matrix_all1s() {
-        let matrix = NumMatrix::new(&[vec![1; 5], vec![1; 5], vec![1; 5], vec![1; 5], vec![1; 5]]);
+        let matrix = NumMatrix::new(&Grid::from_flat(5, 5, vec![1; 25]).unwrap()).unwrap();
 
         assert_eq!(matrix.sum_region(0, 0, 1, 1), 4);
         assert_eq!(matrix.sum_region(0, 0, 3, 3), 16);
@@ -68,8 +86,24 @@ mod test {
 
     #[test]
     fn test_num_matrix_all0s() {
-        let matrix = NumMatrix::new(&[vec![0; 5], vec![0; 5], vec![0; 5], vec![0; 5], vec![0; 5]]);
+        let matrix = NumMatrix::new(&Grid::from_flat(5, 5, vec![0; 25]).unwrap()).unwrap();
 
         assert_eq!(matrix.sum_region(0, 0, 3, 3), 0);
     }
+
+    #[test]
+    fn test_num_matrix_rectangular() {
+        let grid = Grid::from_iter(2, 4, 1..).unwrap();
+        let matrix = NumMatrix::new(&grid).unwrap();
+
+        assert_eq!(matrix.sum_region(0, 0, 1, 3), 36);
+        assert_eq!(matrix.sum_region(1, 1, 1, 2), 13);
+    }
+
+    #[test]
+    fn test_num_matrix_empty() {
+        let grid = Grid::from_rows(vec![]).unwrap();
+
+        assert!(matches!(NumMatrix::new(&grid), Err(NumMatrixError::Empty)));
+    }
 }